@@ -0,0 +1,42 @@
+use math_machines::{Caches, MachineCache};
+use math_machines::phases::{Newable, Phase};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ENTRIES: u128 = 256;
+const LOOKUPS: u32  = 10_000;
+const DIGITS: usize = 4_096;
+
+/// Time the given lookup over every entry of the
+/// cache, `LOOKUPS` times.
+fn time(cache: &mut MachineCache<Vec<u128>, u128>, mut lookup: impl FnMut(&mut MachineCache<Vec<u128>, u128>, u128)) -> Duration {
+    let start = Instant::now();
+    for k in 0..LOOKUPS {
+        lookup(cache, k as u128 % ENTRIES);
+    }
+    start.elapsed()
+}
+
+/// Compare the cost of lookups which clone the
+/// cached phase against those which share it,
+/// where phase results are large, as they would
+/// be for big integers.
+fn main() {
+    let mut cache = MachineCache::new();
+    for n in 0..ENTRIES {
+        let mut phase = Phase::new();
+        phase.setinput(n);
+        phase[0] = vec![n; DIGITS];
+        cache.push(phase);
+    }
+
+    let cloned = time(&mut cache, |cache, n| {
+        black_box(cache.find(n).expect("calculation phase"));
+    });
+    let shared = time(&mut cache, |cache, n| {
+        black_box(cache.find_shared(n).expect("calculation phase"));
+    });
+    println!("find:        {:>10?} per lookup", cloned / LOOKUPS);
+    println!("find_shared: {:>10?} per lookup", shared / LOOKUPS);
+}
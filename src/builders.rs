@@ -0,0 +1,65 @@
+use crate::calculators::{Calculator, Fibonacci, Lucas, Map, Mod, PartialSum, Primes};
+use crate::phases::{MMInt, Phase};
+
+/// Boxed integer calculator, as built by a
+/// `SeqBuilder`.
+pub type BoxedCalculator = Box<dyn Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>>;
+
+/// Builds integer sequences by wrapping some
+/// base calculator, step by step, into a single
+/// boxed calculator.
+///
+/// ```
+/// use math_machines::{Machine, SeqBuilder, lru_calculate};
+///
+/// let sequence = SeqBuilder::fibonacci().map_mod(1000).partial_sum().build();
+/// let machine  = &mut Machine::new(sequence, 128, 50);
+///
+/// // Sum of F(0) through F(20), each mod 1000.
+/// let result   = lru_calculate(machine, 20).expect("partial sum");
+/// assert_eq!(result, 4710);
+/// ```
+pub struct SeqBuilder {
+    calculator: BoxedCalculator,
+}
+
+impl SeqBuilder {
+    /// Finish building, returning the boxed
+    /// calculator.
+    pub fn build(self) -> BoxedCalculator {
+        self.calculator
+    }
+    /// Start building from the Fibonacci
+    /// sequence.
+    pub fn fibonacci() -> Self {
+        Self::from_calculator(Fibonacci{})
+    }
+    /// Start building from some calculator.
+    pub fn from_calculator<MM>(calculator: MM) -> Self
+    where
+        MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>> + 'static,
+    {
+        Self{calculator: Box::new(calculator)}
+    }
+    /// Start building from the Lucas numbers.
+    pub fn lucas() -> Self {
+        Self::from_calculator(Lucas{})
+    }
+    /// Map each value through a function.
+    pub fn map(self, f: impl Fn(MMInt) -> MMInt + 'static) -> Self {
+        Self::from_calculator(Map{inner: self.calculator, f})
+    }
+    /// Reduce each value by a modulus.
+    pub fn map_mod(self, modulus: MMInt) -> Self {
+        Self::from_calculator(Mod{inner: self.calculator, modulus})
+    }
+    /// Sum the values from `0` up to and
+    /// including N.
+    pub fn partial_sum(self) -> Self {
+        Self::from_calculator(PartialSum{inner: self.calculator})
+    }
+    /// Start building from the prime numbers.
+    pub fn primes() -> Self {
+        Self::from_calculator(Primes{})
+    }
+}
@@ -1,12 +1,42 @@
 use crate::phases;
 
-use phases::{Phase, MMSize};
+use phases::{ApproxF64, Phase, MMSize};
 use std::collections::{BTreeSet, HashMap};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+/// Cache of float phases which groups results
+/// within some epsilon.
+///
+/// ```
+/// use math_machines::{Caches, Calculator, Harmonic};
+/// use math_machines::caches::{ApproxCache, MachineCache};
+/// use math_machines::phases::{Newable, Phase};
+/// use ordered_float::OrderedFloat;
+///
+/// let mut phase = Phase::new();
+/// let phase = Harmonic{}.calculate(10, &mut phase).expect("10th harmonic");
+/// let mut drift = phase.clone();
+/// drift[0] = OrderedFloat(phase[0].0 + 1e-12);
+///
+/// let mut cache = MachineCache::new();
+/// cache.push(phase.clone());
+/// cache.push(drift.clone());
+/// assert_eq!(cache.len(), 2);
+///
+/// let mut cache: ApproxCache<_> = MachineCache::new();
+/// cache.push(phase.approx(1e-9));
+/// cache.push(drift.approx(1e-9));
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub type ApproxCache<I> = MachineCache<ApproxF64, I>;
 /// Alias for Result<T, CacheError>.
 pub type CacheResult<T> = Result<T, CacheError>;
+/// Entries and usage counts copied out of a
+/// `MachineCache`.
+pub type CacheSnapshot<T, I> = (Vec<Phase<T, I>>, Vec<(I, MMSize)>);
 /// Test cache. Used only for testing purposes.
 /// Implements the basic needs of the Caches
 /// interface for the test case listed below.
@@ -32,6 +62,41 @@ pub struct TestCache {
     pub entries: BTreeSet<u8>,
     pub usages:  HashMap<u8, usize>
 }
+/// Pools the phase entries of several machines,
+/// each under its own machine identifier, so the
+/// entries of all of them can be inspected in
+/// one place. Like `MachineCache`, it is `Send`
+/// and `Sync` where its phases are.
+///
+/// ```
+/// use math_machines::{Calculator, Fibonacci, Primes};
+/// use math_machines::caches::GlobalCache;
+/// use math_machines::phases::{Newable, Phase};
+///
+/// let mut cache = GlobalCache::new();
+/// for n in 0..5 {
+///     let mut phase = Phase::new();
+///     Fibonacci{}.seed(&mut phase);
+///     cache.push("fibonacci", Fibonacci{}.calculate(n, &mut phase).expect("Nth fibonacci"));
+///     let mut phase = Phase::new();
+///     Primes{}.seed(&mut phase);
+///     cache.push("primes", Primes{}.calculate(n, &mut phase).expect("Nth prime"));
+/// }
+/// assert_eq!(cache.len(), 10);
+///
+/// let found = cache.find("fibonacci", 4).expect("4th fibonacci");
+/// assert_eq!(*found.result(), 3);
+/// let found = cache.find("primes", 4).expect("4th prime");
+/// assert_eq!(*found.result(), 11);
+/// assert!(cache.find("lucas", 4).is_err());
+///
+/// fn is_send<T: Send + Sync>(_: &T) {}
+/// is_send(&cache);
+/// ```
+#[derive(Debug, Default)]
+pub struct GlobalCache<T, I> {
+    machines: HashMap<String, MachineCache<T, I>>,
+}
 /// Manages and maintains phase entries created de
 /// uma mechanismo de math.
 ///
@@ -64,14 +129,26 @@ pub struct TestCache {
 /// assert_eq!(*found.input(), 16);
 ///
 /// assert_eq!(cache.highest_usage(), 2);
+///
+/// fn is_send<T: Send + Sync>(_: &T) {}
+/// is_send(&cache);
 /// ```
 #[derive(Clone, Debug)]
 pub struct MachineCache<T, I> {
-    /// Actual cache entries of `Phase` objects.
-    entries: BTreeSet<Phase<T, I>>,
+    /// Actual cache entries of `Phase` objects,
+    /// shared rather than cloned on lookup. As
+    /// they are shared through `Arc`, the cache is
+    /// `Send` and `Sync` where its phases are.
+    entries: BTreeSet<Arc<Phase<T, I>>>,
     /// Tracks usage count per entry N of the
     /// cache.
     usages:  HashMap<I, MMSize>,
+    /// Tracks when each entry N of the cache was
+    /// pushed.
+    stamps:  HashMap<I, Instant>,
+    /// Age at which entries are invalid, if
+    /// any.
+    ttl:     Option<Duration>,
 }
 /// Error occurred during the manipulation,
 /// retrieval from/updating into a cache, or
@@ -111,6 +188,45 @@ pub trait Caches<K: Hash + ?Sized, V: Sized> {
     fn push(&mut self, entry: V);
 }
 
+/// Compare the entries of two caches, returning
+/// the inputs added and the inputs removed
+/// between them, each in ascending order.
+///
+/// ```
+/// use math_machines::{Caches, Calculator, Fibonacci, MachineCache};
+/// use math_machines::caches::cache_diff;
+/// use math_machines::phases::{Newable, Phase};
+///
+/// let mut cache = MachineCache::new();
+/// let mut warm  = |cache: &mut MachineCache<_, _>, ns: std::ops::Range<u128>| {
+///     for n in ns {
+///         let mut phase = Phase::new();
+///         Fibonacci{}.seed(&mut phase);
+///         cache.push(Fibonacci{}.calculate(n, &mut phase).expect("Nth fibonacci"));
+///     }
+/// };
+/// warm(&mut cache, 0..10);
+/// let before = cache.clone();
+/// warm(&mut cache, 10..15);
+///
+/// let (added, removed) = cache_diff(&before, &cache);
+/// assert_eq!(added, vec![10, 11, 12, 13, 14]);
+/// assert!(removed.is_empty());
+/// ```
+pub fn cache_diff<T, I>(before: &MachineCache<T, I>, after: &MachineCache<T, I>) -> (Vec<I>, Vec<I>)
+where
+    I: Clone + Default + Ord,
+    T: Default,
+{
+    let inputs = |cache: &MachineCache<T, I>| -> BTreeSet<I> {
+        cache.entries.iter().map(|p| p.input().clone()).collect()
+    };
+    let (before, after) = (inputs(before), inputs(after));
+    let added   = after.difference(&before).cloned().collect();
+    let removed = before.difference(&after).cloned().collect();
+    (added, removed)
+}
+
 impl Caches<u8, u8> for TestCache {
     type Cached = u8;
     fn drop(&mut self, key: u8) -> CacheResult<u8> {
@@ -118,7 +234,7 @@ impl Caches<u8, u8> for TestCache {
             Ok(cached) => {
                 self.entries.remove(&cached);
                 self.usages.remove(&cached);
-                Ok(cached.clone())
+                Ok(cached)
             },
             Err(err) => Err(err)
         }
@@ -126,15 +242,14 @@ impl Caches<u8, u8> for TestCache {
     fn drop_invalid(&mut self, _: impl FnMut(&u8) -> bool) -> CacheResult<Vec<u8>> {
         let mut retn = vec![];
         let entries_clone = self.entries.clone();
-        let mut iter      = entries_clone.iter().rev();
 
-        while let Some(cached) = iter.next() {
+        for cached in entries_clone.iter().rev() {
             if true {
                 continue;
             }
             retn.push(cached.to_owned());
             self.entries.remove(cached);
-            self.usages.remove(&cached);
+            self.usages.remove(cached);
         }
         Ok(retn)
     }
@@ -160,15 +275,247 @@ impl Caches<u8, u8> for TestCache {
         }
     }
     fn push(&mut self, entry: u8) {
-        self.usages.insert(entry.clone(), 0);
-        self.entries.insert(entry.clone());
+        self.usages.insert(entry, 0);
+        self.entries.insert(entry);
+    }
+}
+
+impl<T, I> GlobalCache<T, I>
+where
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq + Sized,
+    T: Clone + Debug + Default + Ord + Sized,
+{
+    /// Find a match in the cache of the given
+    /// machine for the given key.
+    pub fn find(&mut self, machine_id: &str, key: I) -> CacheResult<Phase<T, I>> {
+        self.machines
+            .get_mut(machine_id)
+            .ok_or(CacheError::PhaseNotFound)?
+            .find(key)
+    }
+    /// Total number of entries across all
+    /// machines.
+    pub fn len(&self) -> MMSize {
+        self.machines.values().map(|cache| cache.len()).sum()
+    }
+    /// Cache has no entries for any machine.
+    pub fn is_empty(&self) -> bool {
+        self.machines.values().all(|cache| cache.is_empty())
+    }
+    /// Cache of the given machine, if it has any
+    /// entries.
+    pub fn machine(&self, machine_id: &str) -> Option<&MachineCache<T, I>> {
+        self.machines.get(machine_id)
+    }
+    /// Create a new instance of `GlobalCache`
+    /// with no machines.
+    pub fn new() -> Self {
+        Self{machines: HashMap::new()}
+    }
+    /// Push a phase to the cache of the given
+    /// machine.
+    pub fn push(&mut self, machine_id: &str, phase: Phase<T, I>) {
+        self.machines
+            .entry(machine_id.to_owned())
+            .or_default()
+            .push(phase);
     }
 }
 
 impl<T: Sized, I> MachineCache<T, I>
 where
-    I: Hash + Sized + Clone + Copy + Debug + PartialEq + Eq,
+    I: Default + Hash + Sized + Clone + Debug + PartialEq + Eq,
+    T: Default,
 {
+    /// Renormalize the usage counts of this cache
+    /// to a dense ranking of `0..len`, preserving
+    /// the LRU ordering of its entries.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    ///
+    /// for n in [4, 8, 16, 32] {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     cache.push(phase);
+    /// }
+    /// for _ in 0..5 {
+    ///     cache.find(32).expect("calculation phase");
+    /// }
+    /// cache.find(8).expect("calculation phase");
+    /// assert_eq!(cache.highest_usage(), 9);
+    ///
+    /// cache.compact();
+    /// assert_eq!(cache.highest_usage(), cache.len() - 1);
+    /// let order: Vec<_> = [8, 32, 16, 4].iter().map(|n| cache.usage_of(n)).collect();
+    /// assert_eq!(order, vec![Some(0), Some(1), Some(2), Some(3)]);
+    /// ```
+    pub fn compact(&mut self)
+    where
+        I: Ord,
+    {
+        let mut ranked: Vec<_> = self.usages.iter().map(|(i, u)| (*u, i.clone())).collect();
+        ranked.sort();
+        self.usages = ranked
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (_, input))| (input, rank))
+            .collect();
+    }
+    /// Cache has an entry for the given key.
+    pub fn contains(&self, key: &I) -> bool {
+        // Usages are kept with one record per
+        // entry, and are keyed by input.
+        self.usages.contains_key(key)
+    }
+    /// Remove all entries whose input meets the
+    /// predicate from the cache. Returns the
+    /// dropped entries.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    ///
+    /// for n in 0..10 {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     cache.push(phase);
+    /// }
+    /// let dropped = cache.drop_where_input(|n| n % 2 == 1).expect("dropped phases");
+    /// let dropped: Vec<_> = dropped.iter().map(|p| *p.input()).collect();
+    /// assert_eq!(dropped, vec![1, 3, 5, 7, 9]);
+    /// assert_eq!(cache.len(), 5);
+    /// assert!((0..10).all(|n| cache.contains(&n) == (n % 2 == 0)));
+    /// assert_eq!(cache.usage_of(&3), None);
+    /// ```
+    pub fn drop_where_input(&mut self, mut pred: impl FnMut(&I) -> bool) -> CacheResult<Vec<Phase<T, I>>>
+    where
+        I: Ord,
+        T: Clone + Ord,
+    {
+        let dropped: Vec<_> = self.entries
+            .iter()
+            .filter(|p| pred(p.input()))
+            .cloned()
+            .collect();
+        for p in dropped.iter() {
+            self.entries.remove(p);
+            self.usages.remove(p.input());
+            self.stamps.remove(p.input());
+        }
+        Ok(dropped.into_iter().map(|p| (*p).clone()).collect())
+    }
+    /// Rough estimate of the memory held by this
+    /// cache, in bytes, from its entries, the
+    /// shared allocation and reference counts of
+    /// each entry, and its usage and timestamp
+    /// records.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    /// let mut estimates = vec![];
+    ///
+    /// for n in 0..64 {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     cache.push(phase);
+    ///     if [7, 15, 31, 63].contains(&n) {
+    ///         estimates.push(cache.estimated_bytes());
+    ///     }
+    /// }
+    /// assert!(estimates[0] >= 8 * size_of::<Phase<u8, u8>>());
+    /// // Doubling the entries roughly doubles the
+    /// // estimate, as the records grow in steps.
+    /// for pair in estimates.windows(2) {
+    ///     assert!(pair[1] > pair[0] * 3 / 2);
+    ///     assert!(pair[1] < pair[0] * 3);
+    /// }
+    /// ```
+    pub fn estimated_bytes(&self) -> usize {
+        let entry = size_of::<Arc<Phase<T, I>>>() + 2 * size_of::<usize>() + size_of::<Phase<T, I>>();
+        self.len() * entry
+            + self.usages.capacity() * size_of::<(I, MMSize)>()
+            + self.stamps.capacity() * size_of::<(I, Instant)>()
+    }
+    /// Find the entry matching the given key
+    /// exactly, without altering usage counts.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    ///
+    /// for n in [4, 8, 16] {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     cache.push(phase);
+    /// }
+    /// let usage = cache.usage_of(&8);
+    ///
+    /// let found = cache.find_exact(8).expect("calculation phase");
+    /// assert_eq!(*found.input(), 8);
+    /// assert_eq!(cache.usage_of(&8), usage);
+    ///
+    /// assert!(cache.find_exact(9).is_err());
+    /// let found = cache.find_closest(9).expect("calculation phase");
+    /// assert_eq!(*found.input(), 8);
+    /// assert_eq!(cache.usage_of(&8), Some(0));
+    /// ```
+    pub fn find_exact(&self, key: I) -> CacheResult<Phase<T, I>>
+    where
+        T: Clone,
+    {
+        self.entries
+            .iter()
+            .find(|p| *p.input() == key)
+            .map(|p| (**p).clone())
+            .ok_or(CacheError::PhaseNotFound)
+    }
+    /// Find the closest match in the cache for
+    /// the given key, sharing the cached phase
+    /// rather than cloning it.
+    pub fn find_closest_shared(&mut self, key: I) -> CacheResult<Arc<Phase<T, I>>>
+    where
+        I: Ord,
+    {
+        self.find_rev_shared(|ph| ph.input() <= &key)
+    }
+    /// Find a match in the cache for the given
+    /// key, sharing the cached phase rather than
+    /// cloning it.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// use std::sync::Arc;
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    ///
+    /// for n in [4, 8, 16] {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     phase[0] = n * 2;
+    ///     cache.push(phase);
+    /// }
+    /// let found = cache.find_shared(8).expect("calculation phase");
+    /// let again = cache.find_closest_shared(9).expect("calculation phase");
+    /// assert!(Arc::ptr_eq(&found, &again));
+    /// assert_eq!(*found, cache.find(8).expect("calculation phase"));
+    /// assert_eq!(*found.result(), 16);
+    /// assert_eq!(cache.usage_of(&8), Some(0));
+    /// ```
+    pub fn find_shared(&mut self, key: I) -> CacheResult<Arc<Phase<T, I>>> {
+        self.find_rev_shared(|ph| *ph.input() == key)
+    }
+    /// Cache has an entry which has reached its
+    /// TTL.
+    pub fn has_expired(&self) -> bool {
+        self.stamps.keys().any(|key| self.is_expired(key))
+    }
     /// Return the greatest count of iterations
     /// since last visit/use of any value in this
     /// cache.
@@ -177,26 +524,222 @@ where
         us.sort();
         **us.last().unwrap_or(&&0)
     }
+    /// Cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
     /// Number of entries in this cache.
     pub fn len(&self) -> MMSize {
         self.entries.len()
     }
+    /// Largest result of the entries in this
+    /// cache, if any exist.
+    ///
+    /// ```
+    /// use math_machines::{Caches, Calculator, Fibonacci, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::new();
+    ///
+    /// for n in [3, 12, 7] {
+    ///     let mut phase = Phase::new();
+    ///     Fibonacci{}.seed(&mut phase);
+    ///     cache.push(Fibonacci{}.calculate(n, &mut phase).expect("Nth fibonacci"));
+    /// }
+    /// assert_eq!(cache.max_result(), Some(&144));
+    /// assert_eq!(cache.min_result(), Some(&2));
+    ///
+    /// assert_eq!(MachineCache::<u8, u8>::new().max_result(), None);
+    /// ```
+    pub fn max_result(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.entries.iter().map(|p| p.result()).max()
+    }
+    /// Smallest result of the entries in this
+    /// cache, if any exist.
+    pub fn min_result(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.entries.iter().map(|p| p.result()).min()
+    }
     pub fn new() -> Self {
         Self {
             entries: BTreeSet::new(),
-            usages:  HashMap::new()
+            usages:  HashMap::new(),
+            stamps:  HashMap::new(),
+            ttl:     None,
+        }
+    }
+    /// Rebuild the usage counts of this cache from
+    /// its entries, resetting every count to 0 and
+    /// dropping counts of missing entries.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    ///
+    /// let entries: Vec<_> = [4, 8, 16]
+    ///     .into_iter()
+    ///     .map(|n| {
+    ///         let mut phase = Phase::<u8, u8>::new();
+    ///         phase.setinput(n);
+    ///         phase
+    ///     })
+    ///     .collect();
+    /// cache.restore_full(entries, vec![(8, 3)]);
+    /// assert_eq!(cache.usage_of(&8), Some(3));
+    ///
+    /// cache.reindex_usages();
+    /// for n in [4, 8, 16] {
+    ///     assert_eq!(cache.usage_of(&n), Some(0));
+    /// }
+    /// ```
+    pub fn reindex_usages(&mut self) {
+        self.usages = self.entries
+            .iter()
+            .map(|p| (p.input().clone(), 0))
+            .collect();
+    }
+    /// Replace the entries and usage counts of
+    /// this cache with those from a
+    /// `snapshot_full`. Entries without a usage
+    /// count are counted as unused, and counts
+    /// without an entry are dropped.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut cache = MachineCache::<u8, u8>::new();
+    ///
+    /// for n in [4, 8, 16] {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     cache.push(phase);
+    /// }
+    /// cache.find(8).expect("calculation phase");
+    ///
+    /// let (entries, usages) = cache.snapshot_full();
+    /// let mut restored = MachineCache::<u8, u8>::new();
+    /// restored.restore_full(entries, usages);
+    ///
+    /// assert_eq!(restored.len(), cache.len());
+    /// assert_eq!(restored.highest_usage(), cache.highest_usage());
+    /// for n in [4, 8, 16] {
+    ///     assert_eq!(restored.usage_of(&n), cache.usage_of(&n));
+    /// }
+    ///
+    /// let (entries, _) = cache.snapshot_full();
+    /// restored.restore_full(entries, vec![(8, 2), (32, 7)]);
+    /// assert_eq!(restored.usage_of(&4), Some(0));
+    /// assert_eq!(restored.usage_of(&8), Some(2));
+    /// assert_eq!(restored.usage_of(&32), None);
+    /// restored.drop_invalid(|_| true).expect("dropped phases");
+    /// ```
+    pub fn restore_full(&mut self, entries: Vec<Phase<T, I>>, usages: Vec<(I, MMSize)>)
+    where
+        T: Ord,
+        I: Ord,
+    {
+        let now = Instant::now();
+        let mut usages: HashMap<_, _> = usages.into_iter().collect();
+        self.stamps  = entries.iter().map(|p| (p.input().clone(), now)).collect();
+        self.usages  = entries
+            .iter()
+            .map(|p| (p.input().clone(), usages.remove(p.input()).unwrap_or(0)))
+            .collect();
+        self.entries = entries.into_iter().map(Arc::new).collect();
+    }
+    /// Set the age at which entries of this
+    /// cache are dropped as invalid.
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = Some(ttl);
+    }
+    /// Copy out the entries and usage counts of
+    /// this cache, preserving the LRU ordering of
+    /// its entries.
+    pub fn snapshot_full(&self) -> CacheSnapshot<T, I>
+    where
+        T: Clone,
+    {
+        let entries = self.entries.iter().map(|p| (**p).clone()).collect();
+        let usages  = self.usages.iter().map(|(i, u)| (i.clone(), *u)).collect();
+        (entries, usages)
+    }
+    /// Refresh the usage of the entry at the given
+    /// key, aging all others, as `find` does but
+    /// without cloning the phase. Returns whether
+    /// the key exists.
+    ///
+    /// ```
+    /// use math_machines::{Caches, MachineCache};
+    /// use math_machines::phases::{Newable, Phase};
+    /// let mut found   = MachineCache::<u8, u8>::new();
+    /// let mut touched = MachineCache::<u8, u8>::new();
+    ///
+    /// for n in [4, 8, 16, 32] {
+    ///     let mut phase = Phase::<u8, u8>::new();
+    ///     phase.setinput(n);
+    ///     found.push(phase.clone());
+    ///     touched.push(phase);
+    /// }
+    /// for n in [8, 4, 8, 32] {
+    ///     found.find(n).expect("calculation phase");
+    ///     assert!(touched.touch(n));
+    /// }
+    /// let usages = |cache: &MachineCache<u8, u8>| -> Vec<_> {
+    ///     [4, 8, 16, 32].iter().map(|n| cache.usage_of(n)).collect()
+    /// };
+    /// assert_eq!(usages(&found), usages(&touched));
+    ///
+    /// assert!(!touched.touch(9));
+    /// assert_eq!(usages(&found), usages(&touched));
+    /// ```
+    pub fn touch(&mut self, key: I) -> bool {
+        if !self.usages.contains_key(&key) {
+            return false;
+        }
+        self.update_usage(|_| true);
+        self.usages.insert(key, 0);
+        true
+    }
+    /// Usage count of the entry at the given key,
+    /// if it exists.
+    pub fn usage_of(&self, key: &I) -> Option<MMSize> {
+        self.usages.get(key).copied()
+    }
+    /// Find a match that meets the predicate
+    /// searching in reverse order, sharing the
+    /// cached phase.
+    fn find_rev_shared(&mut self, mut pred: impl FnMut(&Phase<T, I>) -> bool) -> CacheResult<Arc<Phase<T, I>>> {
+        match self.entries.iter().rev().find(|p| pred(p)).cloned() {
+            Some(phase) => {
+                self.update_usage(|_| true);
+                self.usages.insert(phase.input().clone(), 0);
+                Ok(phase)
+            },
+            None => Err(CacheError::PhaseNotFound)
+        }
+    }
+    /// Entry at the given key has reached the TTL
+    /// of this cache.
+    fn is_expired(&self, key: &I) -> bool {
+        match (self.ttl, self.stamps.get(key)) {
+            (Some(ttl), Some(stamp)) => stamp.elapsed() >= ttl,
+            _ => false,
         }
     }
     /// Update the usage of individual entry
     /// usages.
     fn update_usage(&mut self, filt: impl FnMut(&(&I, &MMSize)) -> bool) {
         let usage_clone = self.usages.clone();
-        let mut iter = usage_clone.iter().filter(filt);
         // Iterate through the usages, after push
         // of new data, to increment usage by 1
         // for each usage record.
-        while let Some((input, usage)) = iter.next() {
-            self.usages.insert(*input, usage+1);
+        for (input, usage) in usage_clone.iter().filter(filt) {
+            self.usages.insert(input.clone(), usage+1);
         }
     }
     /// Validator to ensure the usage of a value
@@ -208,12 +751,22 @@ where
     }
 }
 
+impl<T: Sized, I> Default for MachineCache<T, I>
+where
+    I: Default + Hash + Sized + Clone + Debug + PartialEq + Eq,
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Going off pattern by implementing Caches traits
 // here to hopefully better illustrate usage
 // specifically for a MathMachine `MachineCache`.
 impl<T, I> Caches<I, Phase<T, I>> for MachineCache<T, I>
 where
-    I: Clone + Copy + Debug + Default + Eq + Hash + Ord + PartialEq + Sized,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq + Sized,
     T: Clone + Debug + Default + Ord + Sized,
 {
     type Cached = Phase<T, I>;
@@ -222,7 +775,8 @@ where
         match self.find(key) {
             Ok(cached) => {
                 self.entries.remove(&cached);
-                self.usages.remove(&cached.input());
+                self.usages.remove(cached.input());
+                self.stamps.remove(cached.input());
                 Ok(cached.clone())
             },
             Err(err) => Err(err)
@@ -231,15 +785,16 @@ where
     fn drop_invalid(&mut self, mut pred: impl FnMut(&Self::Cached) -> bool) -> CacheResult<Vec<Self::Cached>> {
         let mut retn = vec![];
         let entries_clone = self.entries.clone();
-        let mut iter      = entries_clone.iter().rev();
 
-        while let Some(p) = iter.next() {
-            if pred(&p) && self.valid_usage(&p.input()) {
+        for p in entries_clone.iter().rev() {
+            let valid = pred(p) && self.valid_usage(p.input()) && !self.is_expired(p.input());
+            if valid {
                 continue;
             }
-            retn.push(p.to_owned());
+            retn.push((**p).clone());
             self.entries.remove(p);
-            self.usages.remove(&p.input());
+            self.usages.remove(p.input());
+            self.stamps.remove(p.input());
         }
         Ok(retn)
     }
@@ -251,25 +806,17 @@ where
         // preceeding cached phase.
         self.find_rev(|ph| ph.input() <= &key)
     }
-    fn find_rev(&mut self, pred: impl FnMut(&&Self::Cached) -> bool) -> CacheResult<Self::Cached> {
-        let entries_clone = self.entries.clone();
-        let mut iter      = entries_clone.iter().rev();
-
-        match iter.find(pred) {
-            Some(phase) => {
-                self.update_usage(|_| true);
-                self.usages.insert(*phase.input(), 0);
-                Ok(phase.to_owned())
-            },
-            None => Err(CacheError::PhaseNotFound)
-        }
+    fn find_rev(&mut self, mut pred: impl FnMut(&&Self::Cached) -> bool) -> CacheResult<Self::Cached> {
+        self.find_rev_shared(|ph| pred(&ph)).map(|ph| (*ph).clone())
     }
     fn push(&mut self, entry: Self::Cached) {
-        self.entries.insert(entry.clone());
-        self.usages.insert(*entry.input(), 0);
+        let input = entry.input().clone();
+        self.entries.insert(Arc::new(entry));
+        self.usages.insert(input.clone(), 0);
+        self.stamps.insert(input.clone(), Instant::now());
 
         // Filter out entry inputs whose usage
         // count is 0;
-        self.update_usage(|(input, _)| **input != *entry.input());
+        self.update_usage(|(i, _)| **i != input);
     }
 }
@@ -1,7 +1,17 @@
-use crate::phases::{MMFlt, MMInt, Phase};
-use crate::machines::MachineResult;
+use crate::phases::{ApproxF64, Counted, MMFlt, MMInt, MMSInt, MMSize, Newable, Phase};
+use crate::machines::{MachineError, MachineResult};
 
-use std::cmp;
+use ordered_float::OrderedFloat;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug};
+use std::cmp::Ordering;
+#[cfg(feature = "rayon")]
+use std::ops::Range;
+#[cfg(feature = "rayon")]
+use std::sync::RwLock;
 
 /// Type can do some calculation using the
 /// `Calculator` interface.
@@ -10,126 +20,3962 @@ pub trait Calculator<T, I> {
     /// Performs the calculation this machine is
     /// supposed to do.
     fn calculate(&self, n: I, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated>;
+    /// Sets the base case of a new calculation
+    /// into the phase, before any calculation is
+    /// done from scratch. Defaults to leaving the
+    /// phase as is.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, Lucas, Primes, raw_calculate};
+    ///
+    /// let machine = &Machine::new(Fibonacci{}, 128, 50);
+    /// assert_eq!(raw_calculate(machine, 0).expect("0th fibonacci"), 0);
+    ///
+    /// let machine = &Machine::new(Primes{}, 128, 50);
+    /// assert_eq!(raw_calculate(machine, 0).expect("0th prime"), 2);
+    ///
+    /// let machine = &Machine::new(Lucas{}, 128, 50);
+    /// assert_eq!(raw_calculate(machine, 0).expect("0th lucas"), 2);
+    /// ```
+    fn seed(&self, _phase: &mut Self::Calculated) {}
+    /// Input is within the domain of this
+    /// calculator. Defaults to all inputs being
+    /// valid.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Factorial, Fibonacci, lru_calculate};
+    /// use math_machines::machines::MachineError;
+    ///
+    /// let machine = &mut Machine::new(Factorial{}, 128, 50);
+    /// assert!(lru_calculate(machine, 34).is_ok());
+    /// assert!(matches!(lru_calculate(machine, 35), Err(MachineError::InvalidInput)));
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// assert!(lru_calculate(machine, 186).is_ok());
+    /// assert!(matches!(lru_calculate(machine, 187), Err(MachineError::InvalidInput)));
+    /// ```
+    fn valid_input(&self, _n: &I) -> bool {
+        true
+    }
+    /// Closed form of this calculator, if it has
+    /// one. `lru_calculate` prefers the closed
+    /// form, skipping the cache entirely. Defaults
+    /// to having none.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Polygonal, lru_calculate};
+    ///
+    /// let machine = &mut Machine::new(Polygonal{sides: 5}, 128, 50);
+    /// for n in 0..20 {
+    ///     lru_calculate(machine, n).expect("Nth pentagonal");
+    /// }
+    /// assert!(machine.save_state().entries.is_empty());
+    /// ```
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<T, I>> {
+        None
+    }
+    /// Modular form of this calculator, if it has
+    /// one. Used by `Machine::calculate_or_residue`
+    /// where the exact value is out of bounds.
+    /// Defaults to having none.
+    fn as_modular(&self) -> Option<&dyn ModularCalculator<I>> {
+        None
+    }
+}
+
+/// Type can count the iterations it performs
+/// calculating the Nth value from some phase.
+pub trait CountingCalculator<T, I>: Calculator<T, I> {
+    /// Performs the calculation this machine is
+    /// supposed to do, with the number of
+    /// iterations it took.
+    fn calculate_counted(&self, n: I, phase: &mut Self::Calculated) -> MachineResult<(Self::Calculated, MMSize)>;
+}
+
+/// Type can calculate the Nth value directly,
+/// in closed form, independent of any phase.
+pub trait DirectCalculator<T, I> {
+    /// Performs the calculation of the Nth value
+    /// directly.
+    fn calculate_direct(&self, n: I) -> MachineResult<T>;
+}
+
+/// Type can calculate the residue of the Nth
+/// value by some modulus, even where the Nth
+/// value itself is out of bounds.
+///
+/// `Machine::calculate_or_residue` takes any N
+/// rejected by `Calculator::valid_input` as out
+/// of bounds, so calculators exposing this
+/// through `as_modular` must only reject N whose
+/// values overflow.
+pub trait ModularCalculator<I> {
+    /// Performs the calculation of the Nth value
+    /// reduced by the modulus.
+    fn calculate_mod(&self, n: I, modulus: MMInt) -> MachineResult<MMInt>;
+}
+
+/// Advance a phase one step of a linear
+/// recurrence. The phase is rotated, then its
+/// result is set to the sum of each prior value
+/// times its coefficient, the 1st coefficient
+/// applying to the value just before. Errors on
+/// overflow, or if there are more coefficients
+/// than prior values in the phase.
+///
+/// ```
+/// use math_machines::advance_linear;
+/// use math_machines::machines::MachineError;
+/// use math_machines::phases::Phase;
+///
+/// let (mut a, mut b) = (0u128, 1u128);
+/// let mut phase = Phase::from_slice(&[0, 1, 0], 0).expect("a phase");
+/// for _ in 0..50 {
+///     advance_linear(&mut phase, &[1, 1]).expect("next fibonacci");
+///     (a, b) = (a + b, a);
+///     assert_eq!((*phase.result(), *phase.prev()), (a, b));
+/// }
+///
+/// let mut phase = Phase::from_slice(&[u128::MAX, 1, 0], 0).expect("a phase");
+/// assert!(matches!(advance_linear(&mut phase, &[1, 1]), Err(MachineError::Overflow)));
+/// let mut phase = Phase::from_slice(&[2, 1, 0], 0).expect("a phase");
+/// assert!(matches!(advance_linear(&mut phase, &[1, 1, 1]), Err(MachineError::InvalidInput)));
+/// ```
+pub fn advance_linear(phase: &mut Phase<MMInt, MMInt>, coeffs: &[MMInt]) -> MachineResult<()> {
+    if coeffs.len() >= phase[..].len() {
+        return Err(MachineError::InvalidInput);
+    }
+    phase.rotate(1);
+    let mut result: MMInt = 0;
+    for (coeff, value) in coeffs.iter().zip(phase[1..].iter()) {
+        result = coeff
+            .checked_mul(*value)
+            .and_then(|term| result.checked_add(term))
+            .ok_or(MachineError::Overflow)?;
+    }
+    *phase.result_mut() = result;
+    Ok(())
+}
+
+/// Number of Collatz steps for each N of the
+/// range to reach 1, calculated in parallel.
+/// Steps found are shared between threads, so
+/// each N stops at the first value whose steps
+/// are already known. Errors if the range
+/// includes N of 0, or if a value overflows.
+///
+/// ```
+/// use math_machines::collatz_stopping_times;
+/// use math_machines::machines::MachineError;
+///
+/// let steps = |mut n: u128| {
+///     let mut count = 0;
+///     while n != 1 {
+///         n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+///         count += 1;
+///     }
+///     count
+/// };
+/// let expected: Vec<_> = (1..1000).map(steps).collect();
+/// assert_eq!(collatz_stopping_times(1..1000).expect("stopping times"), expected);
+/// assert_eq!(collatz_stopping_times(27..28).expect("stopping times"), vec![111]);
+///
+/// assert!(collatz_stopping_times(0..0).expect("no stopping times").is_empty());
+/// assert!(matches!(collatz_stopping_times(0..10), Err(MachineError::InvalidInput)));
+/// assert!(matches!(
+///     collatz_stopping_times(u128::MAX - 1..u128::MAX),
+///     Err(MachineError::Overflow)));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn collatz_stopping_times(range: Range<MMInt>) -> MachineResult<Vec<MMInt>> {
+    const CHUNK: MMInt = 256;
+    if range.is_empty() {
+        return Ok(vec![]);
+    }
+    if range.start == 0 {
+        return Err(MachineError::InvalidInput);
+    }
+    // Each chunk reads the shared steps under one
+    // lock, and adds the steps it found under
+    // another.
+    let memo = RwLock::new(HashMap::from([(1, 0)]));
+    let chunks: Vec<_> = (range.start..range.end)
+        .step_by(CHUNK as MMSize)
+        .map(|lo| lo..range.end.min(lo.saturating_add(CHUNK)))
+        .collect();
+    let steps = chunks
+        .into_par_iter()
+        .map(|chunk| {
+            let mut found: HashMap<MMInt, MMInt> = HashMap::new();
+            let mut steps = vec![];
+            {
+                let memo = memo.read().expect("collatz memo");
+                for n in chunk {
+                    let (mut path, mut value) = (vec![], n);
+                    let known = loop {
+                        if let Some(steps) = found.get(&value).or_else(|| memo.get(&value)) {
+                            break *steps;
+                        }
+                        path.push(value);
+                        value = if value.is_multiple_of(2) {
+                            value / 2
+                        } else {
+                            value.checked_mul(3)
+                                .and_then(|v| v.checked_add(1))
+                                .ok_or(MachineError::Overflow)?
+                        };
+                    };
+                    for (i, value) in path.iter().rev().enumerate() {
+                        found.insert(*value, known + i as MMInt + 1);
+                    }
+                    steps.push(known + path.len() as MMInt);
+                }
+            }
+            memo.write().expect("collatz memo").extend(found);
+            Ok(steps)
+        })
+        .collect::<MachineResult<Vec<_>>>()?;
+    Ok(steps.into_iter().flatten().collect())
+}
+
+/// Digits of an integer in the given base,
+/// least significant first. Zero has no digits,
+/// and the base must be at least 2.
+///
+/// ```
+/// use math_machines::digits;
+///
+/// assert_eq!(digits(1234, 10), vec![4, 3, 2, 1]);
+/// assert_eq!(digits(255, 16), vec![15, 15]);
+/// assert_eq!(digits(6, 2), vec![0, 1, 1]);
+/// assert!(digits(0, 10).is_empty());
+/// ```
+pub fn digits(mut n: MMInt, base: MMInt) -> Vec<MMInt> {
+    assert!(base >= 2, "base must be at least 2");
+    let mut result = vec![];
+    while n > 0 {
+        result.push(n % base);
+        n /= base;
+    }
+    result
+}
+
+/// Find the Nth integer, counting up from 0 and
+/// starting at N of 1, that satisfies the
+/// predicate. N of 0 is taken as 1. Does not
+/// return if fewer than N integers satisfy the
+/// predicate.
+///
+/// ```
+/// use math_machines::{Machine, Primes, lru_calculate, nth_satisfying};
+///
+/// let machine = &mut Machine::new(Primes{}, 128, 50);
+/// for n in 1..50 {
+///     let nth_prime = lru_calculate(machine, n - 1).expect("Nth prime");
+///     assert_eq!(nth_satisfying(Primes::is_prime, n), nth_prime);
+/// }
+/// assert_eq!(nth_satisfying(|n| n % 2 == 0, 1), 0);
+/// assert_eq!(nth_satisfying(|n| n % 2 == 0, 10), 18);
+/// ```
+pub fn nth_satisfying(mut pred: impl FnMut(MMInt) -> bool, n: MMInt) -> MMInt {
+    let (mut candidate, mut count) = (0, 0);
+    loop {
+        if pred(candidate) {
+            count += 1;
+            if count >= n {
+                return candidate;
+            }
+        }
+        candidate += 1;
+    }
+}
+
+/// Lazily calculate the values of a calculator,
+/// for N from 0 onwards, without any caching.
+/// Each value resumes from the phase of the one
+/// before. Invalid N yield an error, without
+/// ending the iterator.
+///
+/// ```
+/// use math_machines::{Primes, sequence_iter};
+///
+/// let primes: Vec<_> = sequence_iter(Primes{})
+///     .take(10)
+///     .collect::<Result<_, _>>()
+///     .expect("first primes");
+/// assert_eq!(primes, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+/// ```
+pub fn sequence_iter<MM>(mm: MM) -> impl Iterator<Item = MachineResult<MMInt>>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    let mut phase = Phase::new();
+    mm.seed(&mut phase);
+    (0..).map(move |n| {
+        if !mm.valid_input(&n) {
+            return Err(MachineError::InvalidInput);
+        }
+        mm.calculate(n, &mut phase).map(|calc| *calc.result())
+    })
+}
+
+/// Implements the sequence of abundant numbers,
+/// whose proper divisors sum to more than
+/// themselves, to calculate the Nth value
+/// starting at N of 1. Results are cached, with
+/// lookup in reverse order, to find the closest
+/// value calculated to a new N, if N does not
+/// already exist.
+///
+/// ```
+/// use math_machines::{Machine, AbundantNumbers, lru_calculate};
+///
+/// let machine = &mut Machine::new(AbundantNumbers{}, 128, 50);
+/// let result: Vec<_> = (1..6)
+///     .map(|n| lru_calculate(machine, n).expect("Nth abundant"))
+///     .collect();
+/// assert_eq!(result, vec![12, 18, 20, 24, 30]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct AbundantNumbers;
+
+/// Implements the aliquot sequence of some
+/// starting value to calculate the Nth value,
+/// where each value is the sum of the proper
+/// divisors of the one before, ending at 0.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated
+/// to a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Aliquot, lru_calculate};
+///
+/// let machine = &mut Machine::new(Aliquot{start: 12}, 128, 50);
+/// let result: Vec<_> = (0..9)
+///     .map(|n| lru_calculate(machine, n).expect("Nth aliquot"))
+///     .collect();
+/// assert_eq!(result, vec![12, 16, 15, 9, 4, 3, 1, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct Aliquot {
+    pub start: MMInt,
+}
+
+/// Implements the sum of the proper divisors of
+/// N, `σ(N) - N`, to calculate the Nth value.
+/// Each N is calculated independently of any
+/// cached phase.
+///
+/// ```
+/// use math_machines::{Machine, AliquotSum, lru_calculate};
+///
+/// let machine = &mut Machine::new(AliquotSum{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 12).expect("aliquot sum of 12"), 16);
+/// assert_eq!(lru_calculate(machine, 6).expect("aliquot sum of 6"), 6);
+/// assert_eq!(lru_calculate(machine, 1).expect("aliquot sum of 1"), 0);
+/// assert_eq!(lru_calculate(machine, 97).expect("aliquot sum of 97"), 1);
+/// ```
+#[derive(Debug)]
+pub struct AliquotSum;
+
+/// Alternates the sign of the Nth value of some
+/// other float calculator, as `(-1)^n * f(n)`.
+/// Each N is calculated independently of any
+/// cached phase.
+///
+/// ```
+/// use math_machines::{Alternating, Calculator, Machine, PartialSum, lru_calculate};
+/// use math_machines::machines::MachineResult;
+/// use math_machines::phases::Phase;
+/// use ordered_float::OrderedFloat;
+///
+/// #[derive(Debug)]
+/// struct ReciprocalOdd;
+///
+/// impl Calculator<OrderedFloat<f64>, u128> for ReciprocalOdd {
+///     type Calculated = Phase<OrderedFloat<f64>, u128>;
+///     fn calculate(&self, n: u128, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+///         phase.setinput(n);
+///         phase[0] = OrderedFloat(1.0 / (2 * n + 1) as f64);
+///         Ok(phase.to_owned())
+///     }
+/// }
+///
+/// // Leibniz: 1 - 1/3 + 1/5 - 1/7 + ... = pi/4
+/// let leibniz = PartialSum{inner: Alternating{inner: ReciprocalOdd{}}};
+/// let machine = &mut Machine::new(leibniz, 128, 50);
+/// let result  = lru_calculate(machine, 10000).expect("leibniz series");
+/// assert!((result.0 - std::f64::consts::FRAC_PI_4).abs() < 1e-4);
+/// ```
+#[derive(Debug)]
+pub struct Alternating<MM> {
+    pub inner: MM,
+}
+
+/// Implements some float calculator with results
+/// which compare within the given epsilon, so the
+/// cache of a `Machine` groups phases differing
+/// only by rounding.
+///
+/// ```
+/// use math_machines::{Approx, Harmonic, Machine, lru_calculate};
+/// use math_machines::machines::MachineState;
+/// use math_machines::phases::ApproxF64;
+///
+/// let machine = &mut Machine::new(Approx{inner: Harmonic{}, eps: 1e-9}, 128, 50);
+/// let result  = lru_calculate(machine, 10).expect("10th harmonic");
+/// assert!((result.value - 2.9289682539682538).abs() < 1e-9);
+///
+/// let phase = machine.phase_at(10).expect("10th harmonic");
+/// let mut drift = phase.clone();
+/// drift[0] = ApproxF64{value: phase[0].value + 1e-12, eps: 1e-9};
+/// machine.load_state(MachineState{
+///     entries: vec![phase, drift],
+///     usages: vec![(10, 0)],
+///     max_entry_cap: 128,
+///     max_usage_age: 50,
+/// });
+/// assert_eq!(machine.save_state().entries.len(), 1);
+///
+/// let exact = &mut Machine::new(Harmonic{}, 128, 50);
+/// let phase = exact.phase_at(10).expect("10th harmonic");
+/// let mut drift = phase.clone();
+/// drift[0].0 += 1e-12;
+/// exact.load_state(MachineState{
+///     entries: vec![phase, drift],
+///     usages: vec![(10, 0)],
+///     max_entry_cap: 128,
+///     max_usage_age: 50,
+/// });
+/// assert_eq!(exact.save_state().entries.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Approx<MM> {
+    pub inner: MM,
+    pub eps: f64,
+}
+
+/// Implements the arithmetic progression of
+/// some first value and step to calculate the
+/// Nth value. Results are cached, with lookup in
+/// reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Arithmetic, lru_calculate};
+///
+/// let machine = &mut Machine::new(Arithmetic{first: 3, step: 4}, 128, 50);
+/// let result  = lru_calculate(machine, 10).expect("10th term");
+/// assert_eq!(result, 43);
+/// ```
+#[derive(Debug)]
+pub struct Arithmetic {
+    pub first: MMInt,
+    pub step: MMInt,
+}
+
+impl<T, I, C> Calculator<T, I> for Box<C>
+where
+    C: Calculator<T, I> + ?Sized,
+{
+    type Calculated = C::Calculated;
+    fn calculate(&self, n: I, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        (**self).calculate(n, phase)
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        (**self).seed(phase)
+    }
+    fn valid_input(&self, n: &I) -> bool {
+        (**self).valid_input(n)
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<T, I>> {
+        (**self).as_direct()
+    }
+    fn as_modular(&self) -> Option<&dyn ModularCalculator<I>> {
+        (**self).as_modular()
+    }
+}
+
+/// Caps the result of some other calculator at
+/// a maximum value. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Clamp, Fibonacci, lru_calculate};
+///
+/// let machine = &mut Machine::new(Clamp{inner: Fibonacci{}, max: 100}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 11).expect("F(11)"), 89);
+/// for n in 12..20 {
+///     assert_eq!(lru_calculate(machine, n).expect("clamped fibonacci"), 100);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Clamp<MM, T> {
+    pub inner: MM,
+    pub max: T,
+}
+
+/// Implements some sequence given by a closure
+/// of N, without defining a calculator of its
+/// own. Each N is calculated independently of
+/// any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, ClosureCalculator, lru_calculate};
+///
+/// let machine = &mut Machine::new(ClosureCalculator{f: |n| Ok(n * n)}, 128, 50);
+/// let result: Vec<_> = (0..5)
+///     .map(|n| lru_calculate(machine, n).expect("Nth square"))
+///     .collect();
+/// assert_eq!(result, vec![0, 1, 4, 9, 16]);
+///
+/// let cached: Vec<_> = machine.save_state().entries.iter().map(|p| *p.result()).collect();
+/// assert_eq!(cached, result);
+/// ```
+pub struct ClosureCalculator<F> {
+    pub f: F,
+}
+
+/// Implements the number of steps needed for N
+/// to reach 1 under the accelerated Collatz map,
+/// where odd values step to `(3n+1)/2` as a
+/// single step. Each N is calculated independently
+/// of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, CollatzShortcut, lru_calculate};
+///
+/// let machine = &mut Machine::new(CollatzShortcut{}, 128, 50);
+/// let result  = lru_calculate(machine, 27).expect("steps for 27");
+/// assert_eq!(result, 70);
+/// assert_ne!(result, 111);
+/// ```
+#[derive(Debug)]
+pub struct CollatzShortcut;
+
+/// Composes two calculators into a pipeline,
+/// feeding the result of the first calculator as
+/// the N of the second, to calculate `b(a(n))`.
+/// Each N is calculated independently of any
+/// cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Compose, Fibonacci, Primes, lru_calculate};
+///
+/// // prime(2) = 5, fibonacci(5) = 5.
+/// let machine = &mut Machine::new(Compose{a: Primes{}, b: Fibonacci{}}, 128, 50);
+/// let result  = lru_calculate(machine, 2).expect("fibonacci of 2nd prime");
+/// assert_eq!(result, 5);
+/// ```
+#[derive(Debug)]
+pub struct Compose<A, B> {
+    pub a: A,
+    pub b: B,
+}
+
+/// Implements the digital root of N in some
+/// base, by summing its digits until a single
+/// digit remains. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, DigitalRoot, lru_calculate};
+///
+/// let machine = &mut Machine::new(DigitalRoot{base: 16}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 255).expect("0xFF -> 0x1E -> 0xF"), 15);
+///
+/// let machine = &mut Machine::new(DigitalRoot::default(), 128, 50);
+/// assert_eq!(lru_calculate(machine, 493193).expect("digital root"), 2);
+/// assert_eq!(lru_calculate(machine, 0).expect("digital root"), 0);
+///
+/// let machine = &mut Machine::new(DigitalRoot{base: 1}, 128, 50);
+/// assert!(lru_calculate(machine, 10).is_err());
+/// ```
+#[derive(Debug)]
+pub struct DigitalRoot {
+    pub base: MMInt,
+}
+
+/// Implements the Dirichlet convolution of two
+/// arithmetic functions, as calculators, to
+/// calculate `(f*g)(n)`, the sum of `f(d)g(n/d)`
+/// over the divisors `d` of N. Each N is
+/// calculated independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Arithmetic, DirichletConv, lru_calculate};
+///
+/// // Convolving 1 with itself counts divisors.
+/// let one     = || Arithmetic{first: 1, step: 0};
+/// let machine = &mut Machine::new(DirichletConv{f: one(), g: one()}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 6).expect("divisors of 6"), 4);
+/// assert_eq!(lru_calculate(machine, 7).expect("divisors of 7"), 2);
+/// assert_eq!(lru_calculate(machine, 36).expect("divisors of 36"), 9);
+/// ```
+#[derive(Debug)]
+pub struct DirichletConv<F, G> {
+    pub f: F,
+    pub g: G,
+}
+
+/// Implements the number of positive divisors
+/// of N. Each N is calculated independently of
+/// any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, DivisorCount, lru_calculate};
+///
+/// let machine = &mut Machine::new(DivisorCount{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("divisors of 1"), 1);
+/// assert_eq!(lru_calculate(machine, 6).expect("divisors of 6"), 4);
+/// assert_eq!(lru_calculate(machine, 12).expect("divisors of 12"), 6);
+/// assert_eq!(lru_calculate(machine, 16).expect("divisors of 16"), 5);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct DivisorCount;
+
+/// Implements the coefficients of the continued
+/// fraction of e, `[2; 1, 2, 1, 1, 4, 1, 1, 6,
+/// ...]`, to calculate the Nth value. Each N is
+/// calculated independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, EulerNumberCF, lru_calculate};
+///
+/// let machine = &mut Machine::new(EulerNumberCF{}, 128, 50);
+/// let result: Vec<_> = (0..9)
+///     .map(|n| lru_calculate(machine, n).expect("Nth coefficient"))
+///     .collect();
+/// assert_eq!(result, vec![2, 1, 2, 1, 1, 4, 1, 1, 6]);
+/// ```
+#[derive(Debug)]
+pub struct EulerNumberCF;
+
+/// Implements the factorial of N to calculate
+/// the Nth value. Results are cached, with lookup
+/// in reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Factorial, lru_calculate};
+///
+/// let machine = &mut Machine::new(Factorial{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 0).expect("0 factorial"), 1);
+/// assert_eq!(lru_calculate(machine, 10).expect("10 factorial"), 3628800);
+/// ```
+#[derive(Debug)]
+pub struct Factorial;
+
+/// Implements the number of trailing zeros of
+/// `N!`, by Legendre's formula as the sum of
+/// `N / 5^i`, to calculate the Nth value. Each N
+/// is calculated directly, in closed form.
+///
+/// ```
+/// use math_machines::{Machine, FactorialTrailingZeros, lru_calculate};
+///
+/// let machine = &mut Machine::new(FactorialTrailingZeros{}, 128, 50);
+/// let result: Vec<_> = [4, 5, 25, 100]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("trailing zeros of N!"))
+///     .collect();
+/// assert_eq!(result, vec![0, 1, 6, 24]);
+/// ```
+#[derive(Debug)]
+pub struct FactorialTrailingZeros;
+
+/// Implements the Fibonacci sequence to calculate
+/// the Nth value. Results are cached, with lookup
+/// in reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Fibonacci, lru_calculate};
+///
+/// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+/// let result  = lru_calculate(machine, 26).expect("26th fibonacci");
+/// assert_eq!(result, 121393);
+/// ```
+#[derive(Debug)]
+pub struct Fibonacci;
+
+/// Implements the number of decimal digits of
+/// the Nth Fibonacci number. Digits are counted
+/// exactly while `F(n)` fits within `MMInt`, and
+/// past that by the closed form
+/// `floor(n*log10(φ) - log10(sqrt(5))) + 1`,
+/// without calculating the number itself. The
+/// closed form is only precise enough in `f64`
+/// up to N of 10^8. Each N is calculated
+/// directly.
+///
+/// ```
+/// use math_machines::{Machine, Fibonacci, FibonacciDigitCount, digits, lru_calculate};
+/// use math_machines::machines::MachineError;
+///
+/// let machine = &mut Machine::new(FibonacciDigitCount{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 10).expect("digits of F(10)"), 2);
+/// assert_eq!(lru_calculate(machine, 100).expect("digits of F(100)"), 21);
+///
+/// let fibonacci = &mut Machine::new(Fibonacci{}, 128, 50);
+/// for n in 0..=186 {
+///     let value = lru_calculate(fibonacci, n).expect("Nth fibonacci");
+///     let count = digits(value, 10).len().max(1) as u128;
+///     assert_eq!(lru_calculate(machine, n).expect("digits of F(n)"), count);
+/// }
+/// assert_eq!(lru_calculate(machine, 187).expect("digits of F(187)"), 39);
+/// assert_eq!(lru_calculate(machine, 1000).expect("digits of F(1000)"), 209);
+/// assert!(lru_calculate(machine, 100_000_000).is_ok());
+/// assert!(matches!(lru_calculate(machine, 100_000_001), Err(MachineError::InvalidInput)));
+/// ```
+#[derive(Debug)]
+pub struct FibonacciDigitCount;
+
+/// Implements the Fibonacci sequence, reduced by
+/// some modulus, to calculate the Nth value.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated to
+/// a new N, if N does not already exist. When
+/// `fast`, N far from the closest value is first
+/// reduced by the Pisano period of the modulus.
+///
+/// ```
+/// use math_machines::{Machine, FibonacciMod, lru_calculate};
+///
+/// let machine = &mut Machine::new(FibonacciMod{modulus: 1000, fast: false}, 128, 50);
+/// let result  = lru_calculate(machine, 26).expect("26th fibonacci mod 1000");
+/// assert_eq!(result, 393);
+///
+/// let slow = &mut Machine::new(FibonacciMod{modulus: 97, fast: false}, 128, 50);
+/// let fast = &mut Machine::new(FibonacciMod{modulus: 97, fast: true}, 128, 50);
+/// assert_eq!(
+///     lru_calculate(fast, 1_000_000).expect("Nth fibonacci mod 97"),
+///     lru_calculate(slow, 1_000_000).expect("Nth fibonacci mod 97"));
+///
+/// // 10^30 is 40 modulo the period 60, where F(40) is 102334155.
+/// let fast = &mut Machine::new(FibonacciMod{modulus: 10, fast: true}, 128, 50);
+/// assert_eq!(lru_calculate(fast, 10u128.pow(30)).expect("Nth fibonacci mod 10"), 5);
+///
+/// // N near the closest value is never reduced, so the
+/// // period of a large modulus is not searched for.
+/// let fast = &mut Machine::new(FibonacciMod{modulus: 10u128.pow(30), fast: true}, 128, 50);
+/// assert_eq!(lru_calculate(fast, 100).expect("Nth fibonacci"), 354224848179261915075);
+///
+/// let zero = &mut Machine::new(FibonacciMod{modulus: 0, fast: true}, 128, 50);
+/// assert!(lru_calculate(zero, 10).is_err());
+/// ```
+#[derive(Debug)]
+pub struct FibonacciMod {
+    pub modulus: MMInt,
+    pub fast:    bool,
+}
+
+/// Memoizes the exact result of some other
+/// calculator for each N, without bound. Prefer
+/// this over the LRU cache of a `Machine` for
+/// calculators where each N is calculated
+/// independently of any cached phase, so cannot
+/// resume from the closest value, and the same
+/// N are queried repeatedly. Prefer the LRU
+/// cache alone for sequences that can resume,
+/// or where memory must be bounded.
+///
+/// ```
+/// use math_machines::{Machine, CollatzShortcut, FullMemo, Map, raw_calculate};
+/// use std::cell::Cell;
+///
+/// let calls   = Cell::new(0);
+/// let counted = Map{inner: CollatzShortcut{}, f: |v| { calls.set(calls.get() + 1); v }};
+/// let machine = &Machine::new(FullMemo::new(counted), 128, 50);
+/// for _ in 0..5 {
+///     assert_eq!(raw_calculate(machine, 27).expect("steps for 27"), 70);
+/// }
+/// assert_eq!(calls.get(), 1);
+/// ```
+#[derive(Debug)]
+pub struct FullMemo<MM, T> {
+    pub inner: MM,
+    pub memo: RefCell<HashMap<MMInt, Phase<T, MMInt>>>,
+}
+
+/// Implements the sequence of integers which
+/// are the sum of two squares, the norms of the
+/// Gaussian integers, to calculate the Nth value,
+/// where `0 = 0^2 + 0^2` is the 0th value.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated to
+/// a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, GaussianNorm, lru_calculate};
+///
+/// let machine = &mut Machine::new(GaussianNorm{}, 128, 50);
+/// let result: Vec<_> = (0..9)
+///     .map(|n| lru_calculate(machine, n).expect("Nth norm"))
+///     .collect();
+/// assert_eq!(result, vec![0, 1, 2, 4, 5, 8, 9, 10, 13]);
+/// ```
+#[derive(Debug)]
+pub struct GaussianNorm;
+
+/// Implements the greatest common divisor of N
+/// and some fixed M. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, GcdWith, lru_calculate};
+///
+/// let machine = &mut Machine::new(GcdWith{m: 12}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 8).expect("gcd of 8 and 12"), 4);
+/// assert_eq!(lru_calculate(machine, 9).expect("gcd of 9 and 12"), 3);
+/// assert_eq!(lru_calculate(machine, 7).expect("gcd of 7 and 12"), 1);
+/// ```
+#[derive(Debug)]
+pub struct GcdWith {
+    pub m: MMInt,
+}
+
+/// Implements the convergents of the golden
+/// ratio, `F(n+2)/F(n+1)`, to calculate the Nth
+/// value. Results are cached, with lookup in
+/// reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+#[derive(Debug)]
+pub struct GoldenRatio;
+
+/// Implements the greatest prime factor of N,
+/// taking 1 for N of 1, to calculate the Nth
+/// value. Each N is calculated independently of
+/// any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, GreatestPrimeFactor, lru_calculate};
+///
+/// let machine = &mut Machine::new(GreatestPrimeFactor{}, 128, 50);
+/// let result: Vec<_> = [12, 17, 100, 1]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("greatest prime factor"))
+///     .collect();
+/// assert_eq!(result, vec![3, 17, 5, 1]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct GreatestPrimeFactor;
+
+/// Implements the Harmonic series to calculate
+/// the Nth value. Results are cached, with lookup
+/// in reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+#[derive(Debug)]
+pub struct Harmonic;
+
+/// Implements the Harmonic series, as an
+/// interval of lower and upper bounds on the
+/// true value, to calculate the Nth value. Each
+/// rounded step widens the bounds outwards by a
+/// unit in the last place. Results are cached,
+/// with lookup in reverse order, to find the
+/// closest value calculated to a new N, if N
+/// does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, HarmonicExact, HarmonicInterval, lru_calculate};
+///
+/// let exact   = &mut Machine::new(HarmonicExact{}, 128, 50);
+/// let machine = &mut Machine::new(HarmonicInterval{}, 128, 50);
+/// for n in 1..=40 {
+///     let (p, q)         = lru_calculate(exact, n).expect("Nth harmonic");
+///     let (lower, upper) = lru_calculate(machine, n).expect("Nth harmonic bounds");
+///     let value = p as f64 / q as f64;
+///     assert!(lower.0 <= value && value <= upper.0);
+///     assert!(upper.0 - lower.0 < 1e-12);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HarmonicInterval;
+
+/// Implements the Harmonic series, as exact
+/// fractions of numerator and denominator in
+/// lowest terms, to calculate the Nth value.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated
+/// to a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, HarmonicExact, lru_calculate};
+///
+/// let machine = &mut Machine::new(HarmonicExact{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("1st harmonic"), (1, 1));
+/// assert_eq!(lru_calculate(machine, 2).expect("2nd harmonic"), (3, 2));
+/// assert_eq!(lru_calculate(machine, 4).expect("4th harmonic"), (25, 12));
+/// assert!(lru_calculate(machine, 200).is_err());
+/// ```
+#[derive(Debug)]
+pub struct HarmonicExact;
+
+/// Implements the hexagonal numbers, `n(2n-1)`,
+/// to calculate the Nth value. Each N is
+/// calculated independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Hexagonal, lru_calculate};
+///
+/// let machine = &mut Machine::new(Hexagonal{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("1st hexagonal"), 1);
+/// assert_eq!(lru_calculate(machine, 5).expect("5th hexagonal"), 45);
+/// assert!(lru_calculate(machine, 13_043_817_825_332_782_212).is_ok());
+/// assert!(lru_calculate(machine, 13_043_817_825_332_782_213).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Hexagonal;
+
+/// Implements the hyperfactorials, the product
+/// of `k^k` for k up to N, to calculate the Nth
+/// value. Results are cached, with lookup in
+/// reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Hyperfactorial, lru_calculate};
+/// use math_machines::machines::MachineError;
+///
+/// let machine = &mut Machine::new(Hyperfactorial{}, 128, 50);
+/// let result: Vec<_> = (1..4)
+///     .map(|n| lru_calculate(machine, n).expect("Nth hyperfactorial"))
+///     .collect();
+/// assert_eq!(result, vec![1, 4, 108]);
+/// assert_eq!(lru_calculate(machine, 6).expect("6th hyperfactorial"), 4031078400000);
+/// assert!(lru_calculate(machine, 9).is_ok());
+/// assert!(matches!(lru_calculate(machine, 10), Err(MachineError::InvalidInput)));
+/// ```
+#[derive(Debug)]
+pub struct Hyperfactorial;
+
+/// Instruments some other calculator, pairing
+/// each result with the running count of
+/// iterations the inner calculator reports
+/// taking to reach it from its seed. Resuming
+/// from a cached phase adds to the count of
+/// that phase, so the count of N is the same
+/// however it was reached.
+///
+/// ```
+/// use math_machines::{Machine, Instrumented, Primes, lru_calculate, raw_calculate};
+///
+/// let machine = &mut Machine::new(Instrumented{inner: Primes{}}, 128, 50);
+/// let small = raw_calculate(machine, 10).expect("10th prime");
+/// let large = raw_calculate(machine, 20).expect("20th prime");
+/// assert_eq!((small.value, large.value), (31, 73));
+/// assert!(small.steps < large.steps);
+///
+/// // Resumed from the cached 20th prime.
+/// lru_calculate(machine, 20).expect("20th prime");
+/// let found = lru_calculate(machine, 25).expect("25th prime");
+/// assert_eq!(found.value, 101);
+/// assert_eq!(found.steps, raw_calculate(machine, 25).expect("25th prime").steps);
+///
+/// // One entry per N, however often it is
+/// // calculated, even once entries are evicted.
+/// let machine = &mut Machine::new(Instrumented{inner: Primes{}}, 4, 2);
+/// let first = lru_calculate(machine, 20).expect("20th prime");
+/// let again = lru_calculate(machine, 20).expect("20th prime");
+/// assert_eq!((first.value, first.steps), (again.value, again.steps));
+/// for n in 21..30 {
+///     lru_calculate(machine, n).expect("Nth prime");
+/// }
+/// assert_eq!(machine.save_state().entries.len(), machine.save_state().usages.len());
+/// ```
+#[derive(Debug)]
+pub struct Instrumented<MM> {
+    pub inner: MM,
+}
+
+/// Implements the sequence of Keith numbers,
+/// which appear in the sequence started by their
+/// own digits, where each following value is the
+/// sum of as many values before it as there are
+/// digits, to calculate the Nth value, where
+/// `K(0) = 14`. Results are cached, with lookup
+/// in reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Keith, lru_calculate};
+///
+/// let machine = &mut Machine::new(Keith{}, 128, 50);
+/// let result: Vec<_> = (0..6)
+///     .map(|n| lru_calculate(machine, n).expect("Nth keith"))
+///     .collect();
+/// assert_eq!(result, vec![14, 19, 28, 47, 61, 75]);
+/// assert_eq!(lru_calculate(machine, 10).expect("10th keith"), 2208);
+/// ```
+#[derive(Debug)]
+pub struct Keith;
+
+/// Implements the least prime factor of N,
+/// taking 1 for N of 1, to calculate the Nth
+/// value. Each N is calculated independently of
+/// any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, LeastPrimeFactor, lru_calculate};
+///
+/// let machine = &mut Machine::new(LeastPrimeFactor{}, 128, 50);
+/// let result: Vec<_> = [15, 49, 13, 1]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("least prime factor"))
+///     .collect();
+/// assert_eq!(result, vec![3, 7, 13, 1]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct LeastPrimeFactor;
+
+/// Implements the Leonardo numbers, where
+/// `L(0) = L(1) = 1` and `L(n) = L(n-1) + L(n-2) + 1`,
+/// to calculate the Nth value. Results are
+/// cached, with lookup in reverse order, to find
+/// the closest value calculated to a new N, if N
+/// does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Leonardo, lru_calculate};
+///
+/// let machine = &mut Machine::new(Leonardo{}, 128, 50);
+/// let result: Vec<_> = (0..6)
+///     .map(|n| lru_calculate(machine, n).expect("Nth leonardo"))
+///     .collect();
+/// assert_eq!(result, vec![1, 1, 3, 5, 9, 15]);
+/// assert!(lru_calculate(machine, 200).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Leonardo;
+
+/// Implements the Liouville function to
+/// calculate the Nth value, as `(-1)^Ω(N)` where
+/// `Ω(N)` counts the prime factors of N with
+/// multiplicity. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Liouville, lru_calculate};
+///
+/// let machine = &mut Machine::new(Liouville{}, 128, 50);
+/// let result: Vec<_> = [1, 2, 4, 12]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("Nth liouville"))
+///     .collect();
+/// assert_eq!(result, vec![1, -1, 1, -1]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Liouville;
+
+/// Implements the sequence of Lucas numbers to
+/// calculate the Nth value, where `L(0) = 2` and
+/// `L(1) = 1`. Results are cached, with lookup in
+/// reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Lucas, lru_calculate};
+///
+/// let machine = &mut Machine::new(Lucas{}, 128, 50);
+/// let result  = lru_calculate(machine, 10).expect("10th lucas");
+/// assert_eq!(result, 123);
+/// ```
+#[derive(Debug)]
+pub struct Lucas;
+
+/// Implements the Lucas sequence `U(P, Q)` to
+/// calculate the Nth value, where `U(0) = 0`,
+/// `U(1) = 1` and `U(n) = P*U(n-1) - Q*U(n-2)`.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated to
+/// a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, LucasSequence, lru_calculate};
+///
+/// // Fibonacci numbers are U(1, -1).
+/// let machine = &mut Machine::new(LucasSequence{p: 1, q: -1}, 128, 50);
+/// let result  = lru_calculate(machine, 26).expect("26th fibonacci");
+/// assert_eq!(result, 121393);
+///
+/// // Pell numbers are U(2, -1).
+/// let machine = &mut Machine::new(LucasSequence{p: 2, q: -1}, 128, 50);
+/// let result  = lru_calculate(machine, 6).expect("6th pell");
+/// assert_eq!(result, 70);
+///
+/// let result  = lru_calculate(machine, 200);
+/// assert!(result.is_err());
+/// ```
+#[derive(Debug)]
+pub struct LucasSequence {
+    pub p: MMSInt,
+    pub q: MMSInt,
+}
+
+/// Maps the result of some other calculator
+/// through a function. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Fibonacci, Map, lru_calculate};
+///
+/// let machine = &mut Machine::new(Map{inner: Fibonacci{}, f: |v| v * 2}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 10).expect("2 * F(10)"), 110);
+/// ```
+pub struct Map<MM, F> {
+    pub inner: MM,
+    pub f: F,
+}
+
+/// Implements the Mertens function to calculate
+/// the Nth value, as the running sum of the
+/// Möbius function `μ(1) + ... + μ(N)`.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated
+/// to a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Mertens, lru_calculate};
+///
+/// let machine = &mut Machine::new(Mertens{}, 128, 50);
+/// let result: Vec<_> = [1, 2, 4, 10]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("Nth mertens"))
+///     .collect();
+/// assert_eq!(result, vec![1, 0, -1, -1]);
+/// ```
+#[derive(Debug)]
+pub struct Mertens;
+
+/// Implements the Möbius function to calculate
+/// the Nth value, as 0 if N has a squared prime
+/// factor, otherwise `(-1)^k` for its k prime
+/// factors. Each N is calculated independently
+/// of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Mobius, lru_calculate};
+///
+/// let machine = &mut Machine::new(Mobius{}, 128, 50);
+/// let result: Vec<_> = [1, 2, 6, 4, 30]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("Nth mobius"))
+///     .collect();
+/// assert_eq!(result, vec![1, -1, 1, 0, -1]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Mobius;
+
+/// Reduces the result of some other calculator
+/// by a modulus. Each N is calculated
+/// independently of any cached phase. The inner
+/// calculator may still overflow before its
+/// result is reduced; prefer `FibonacciMod` for
+/// Fibonacci values past the bounds of `MMInt`.
+///
+/// ```
+/// use math_machines::{Machine, Factorial, Mod, lru_calculate};
+///
+/// let machine = &mut Machine::new(Mod{inner: Factorial{}, modulus: 1000}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 5).expect("5! mod 1000"), 120);
+/// assert_eq!(lru_calculate(machine, 10).expect("10! mod 1000"), 800);
+///
+/// let machine = &mut Machine::new(Mod{inner: Factorial{}, modulus: 0}, 128, 50);
+/// assert!(lru_calculate(machine, 5).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Mod<MM> {
+    pub inner: MM,
+    pub modulus: MMInt,
+}
+
+/// Implements the partial sums of some other
+/// calculator, the sum of its values from `0` up
+/// to and including N. Results are
+/// cached, with lookup in reverse order, to find
+/// the closest value calculated to a new N, if N
+/// does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Fibonacci, Harmonic, PartialSum, lru_calculate};
+///
+/// // Sum of H(0), H(1), H(2) and H(3).
+/// let machine = &mut Machine::new(PartialSum{inner: Harmonic{}}, 128, 50);
+/// let result  = lru_calculate(machine, 3).expect("partial sum");
+/// assert!((result.0 - (0.0 + 1.0 + 1.5 + 11.0/6.0)).abs() < 1e-12);
+///
+/// // Sum of F(0) through F(10).
+/// let machine = &mut Machine::new(PartialSum{inner: Fibonacci{}}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 10).expect("partial sum"), 143);
+/// ```
+#[derive(Debug)]
+pub struct PartialSum<MM> {
+    pub inner: MM,
+}
+
+/// Implements the sum of the first N prime
+/// numbers to calculate the Nth value. Results
+/// are cached, with lookup in reverse order, to
+/// find the closest value calculated to a new N,
+/// if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, PrimeSum, lru_calculate};
+///
+/// let machine = &mut Machine::new(PrimeSum{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("sum of 1 prime"), 2);
+/// assert_eq!(lru_calculate(machine, 5).expect("sum of 5 primes"), 28);
+/// assert_eq!(lru_calculate(machine, 10).expect("sum of 10 primes"), 129);
+/// ```
+#[derive(Debug)]
+pub struct PrimeSum;
+
+/// Implements the sequence of narcissistic
+/// numbers, equal to the sum of their own digits
+/// in some base each raised to the number of
+/// digits, to calculate the Nth value. Results
+/// are cached, with lookup in reverse order, to
+/// find the closest value calculated to a new N,
+/// if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Narcissistic, lru_calculate};
+///
+/// let machine = &mut Machine::new(Narcissistic::default(), 128, 50);
+/// assert_eq!(lru_calculate(machine, 8).expect("8th narcissistic"), 8);
+/// assert_eq!(lru_calculate(machine, 10).expect("10th narcissistic"), 153);
+///
+/// // 1, 2, 5 = 1^2 + 2^2 and 8 = 2^2 + 2^2 in base 3.
+/// let machine = &mut Machine::new(Narcissistic{base: 3}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 4).expect("4th narcissistic"), 8);
+///
+/// let machine = &mut Machine::new(Narcissistic{base: 1}, 128, 50);
+/// assert!(lru_calculate(machine, 4).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Narcissistic {
+    pub base: MMInt,
+}
+
+/// Implements the sequence of palindromic
+/// numbers, which read the same forwards and
+/// backwards, to calculate the Nth value. Each
+/// value is constructed from the one before.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated
+/// to a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Palindrome, lru_calculate};
+///
+/// let machine = &mut Machine::new(Palindrome{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 9).expect("10th palindrome"), 9);
+/// assert_eq!(lru_calculate(machine, 10).expect("11th palindrome"), 11);
+/// assert_eq!(lru_calculate(machine, 18).expect("19th palindrome"), 99);
+/// assert_eq!(lru_calculate(machine, 19).expect("20th palindrome"), 101);
+/// assert_eq!(lru_calculate(machine, 200).expect("201st palindrome"), 10101);
+/// ```
+#[derive(Debug)]
+pub struct Palindrome;
+
+/// Implements the decimal digits of Pi to
+/// calculate the Nth digit, where the 0th digit
+/// is the leading `3`. Digits calculated are
+/// kept as history, and are extended by
+/// resuming a bounded spigot for a new N. The
+/// spigot is exact only for as many digits as
+/// it is sized for, past which it restarts at
+/// twice the size.
+///
+/// ```
+/// use math_machines::{Machine, PiDigits, lru_calculate};
+///
+/// let machine = &mut Machine::new(PiDigits::default(), 128, 50);
+/// let result: Vec<_> = (0..6)
+///     .map(|n| lru_calculate(machine, n).expect("Nth digit of pi"))
+///     .collect();
+/// assert_eq!(result, vec![3, 1, 4, 1, 5, 9]);
+///
+/// let result  = lru_calculate(machine, 50).expect("50th digit of pi");
+/// assert_eq!(result, 0);
+///
+/// let stepped = &mut Machine::new(PiDigits::default(), 1024, 1024);
+/// let at_once = &mut Machine::new(PiDigits::default(), 128, 50);
+/// for n in 0..300 {
+///     lru_calculate(stepped, n).expect("Nth digit of pi");
+/// }
+/// lru_calculate(at_once, 299).expect("299th digit of pi");
+/// for n in 0..300 {
+///     assert_eq!(lru_calculate(stepped, n).ok(), lru_calculate(at_once, n).ok());
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PiDigits {
+    pub history: RefCell<Vec<MMInt>>,
+    spigot: RefCell<PiSpigot>,
+}
+
+/// State of the bounded spigot of `PiDigits`,
+/// kept between calculations to resume from.
+#[derive(Debug, Default)]
+struct PiSpigot {
+    remainders: Vec<MMInt>,
+    /// Count of digits the spigot is exact for.
+    capacity: MMSize,
+    /// Count of steps taken, each yielding up to
+    /// one more digit.
+    steps: MMSize,
+    /// Count of digits released, including the
+    /// leading zero the spigot begins with.
+    released: MMSize,
+    predigit: MMInt,
+    nines: MMSize,
+}
+
+/// Implements the polygonal numbers of some
+/// number of sides, `((s-2)n^2 - (s-4)n) / 2`, to
+/// calculate the Nth value. Each N is calculated
+/// directly, in closed form.
+///
+/// ```
+/// use math_machines::{Machine, Polygonal, lru_calculate};
+///
+/// let machine = &mut Machine::new(Polygonal{sides: 3}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 4).expect("4th triangular"), 10);
+///
+/// let machine = &mut Machine::new(Polygonal{sides: 5}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 5).expect("5th pentagonal"), 35);
+///
+/// let machine = &mut Machine::new(Polygonal{sides: 2}, 128, 50);
+/// assert!(lru_calculate(machine, 5).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Polygonal {
+    pub sides: MMInt,
+}
+
+/// Implements the sequence of prime numbers to
+/// calculate the Nth value in the sequence.
+/// Results are cached, with lookup
+/// in reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, Primes, lru_calculate};
+///
+/// let machine = &mut Machine::new(Primes{}, 128, 50);
+/// let result  = lru_calculate(machine, 25).expect("25th prime");
+/// assert_eq!(result, 101);
+/// ```
+#[derive(Debug)]
+pub struct Primes;
+
+/// Implements the rank of apparition of N, the
+/// smallest positive M where N divides `F(M)`,
+/// to calculate the Nth value. Each N is
+/// calculated independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, RankOfApparition, lru_calculate};
+///
+/// let machine = &mut Machine::new(RankOfApparition{}, 128, 50);
+/// let result: Vec<_> = [2, 3, 5, 7]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("rank of apparition"))
+///     .collect();
+/// assert_eq!(result, vec![3, 4, 5, 8]);
+/// assert_eq!(lru_calculate(machine, 1).expect("rank of apparition"), 1);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct RankOfApparition;
+
+/// Implements the ratio of the Nth value of some
+/// other calculator to the value before it. Each
+/// N is calculated independently of any cached
+/// phase. Errors for N of 0, or where the value
+/// before is 0.
+///
+/// ```
+/// use math_machines::{Calculator, Machine, Fibonacci, PartialSum, Ratio, lru_calculate};
+/// use math_machines::machines::MachineError;
+/// use math_machines::phases::{Newable, Phase};
+///
+/// let machine = &mut Machine::new(Ratio{inner: Fibonacci{}}, 128, 50);
+/// let result  = lru_calculate(machine, 30).expect("F(30)/F(29)");
+/// assert!((result.0 - (1.0 + 5f64.sqrt()) / 2.0).abs() < 1e-9);
+///
+/// assert!(lru_calculate(machine, 0).is_err());
+/// assert!(matches!(lru_calculate(machine, 1), Err(MachineError::DivisionByZero)));
+///
+/// let ratio = Ratio{inner: Fibonacci{}};
+/// assert!(matches!(ratio.calculate(0, &mut Phase::new()), Err(MachineError::InvalidInput)));
+/// let sums = PartialSum{inner: Ratio{inner: Fibonacci{}}};
+/// assert!(sums.calculate(3, &mut Phase::new()).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Ratio<MM> {
+    pub inner: MM,
+}
+
+/// Implements the reciprocal of the Nth value of
+/// some other calculator. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Arithmetic, Calculator, Machine, PartialSum, Reciprocal, lru_calculate};
+/// use math_machines::machines::{MachineError, MachineResult};
+/// use math_machines::phases::Phase;
+///
+/// #[derive(Debug)]
+/// struct Squares;
+///
+/// impl Calculator<u128, u128> for Squares {
+///     type Calculated = Phase<u128, u128>;
+///     fn calculate(&self, n: u128, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+///         phase.setinput(n);
+///         phase[0] = (n + 1).pow(2);
+///         Ok(phase.to_owned())
+///     }
+/// }
+///
+/// // Basel: 1 + 1/4 + 1/9 + ... = pi^2/6
+/// let basel   = PartialSum{inner: Reciprocal{inner: Squares{}}};
+/// let machine = &mut Machine::new(basel, 128, 50);
+/// let result  = lru_calculate(machine, 10000).expect("basel series");
+/// assert!((result.0 - std::f64::consts::PI.powi(2) / 6.0).abs() < 1e-3);
+///
+/// let machine = &mut Machine::new(Reciprocal{inner: Arithmetic{first: 0, step: 1}}, 128, 50);
+/// assert!(matches!(lru_calculate(machine, 0), Err(MachineError::DivisionByZero)));
+/// assert_eq!(lru_calculate(machine, 4).expect("1/4").0, 0.25);
+/// ```
+#[derive(Debug)]
+pub struct Reciprocal<MM> {
+    pub inner: MM,
+}
+
+/// Implements the repunits, `(10^N - 1) / 9` or N
+/// ones in decimal, to calculate the Nth value.
+/// Results are cached, with lookup in reverse
+/// order, to find the closest value calculated
+/// to a new N, if N does not already exist.
+///
+/// ```
+/// use math_machines::{Machine, Repunit, lru_calculate};
+/// use math_machines::machines::MachineError;
+///
+/// let machine = &mut Machine::new(Repunit{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("1st repunit"), 1);
+/// assert_eq!(lru_calculate(machine, 3).expect("3rd repunit"), 111);
+/// assert!(lru_calculate(machine, 39).is_ok());
+/// assert!(matches!(lru_calculate(machine, 40), Err(MachineError::InvalidInput)));
+/// ```
+#[derive(Debug)]
+pub struct Repunit;
+
+/// Implements N with its digits in some base
+/// reversed, dropping any leading zeros. Each N
+/// is calculated independently of any cached
+/// phase.
+///
+/// ```
+/// use math_machines::{Machine, Reverse, lru_calculate};
+///
+/// let machine = &mut Machine::new(Reverse::default(), 128, 50);
+/// assert_eq!(lru_calculate(machine, 1234).expect("reversed 1234"), 4321);
+/// assert_eq!(lru_calculate(machine, 1200).expect("reversed 1200"), 21);
+/// assert_eq!(lru_calculate(machine, 5).expect("reversed 5"), 5);
+///
+/// let machine = &mut Machine::new(Reverse{base: 2}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 0b1101).expect("reversed 0b1101"), 0b1011);
+///
+/// let machine = &mut Machine::new(Reverse{base: 0}, 128, 50);
+/// assert!(lru_calculate(machine, 1234).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Reverse {
+    pub base: MMInt,
+}
+
+/// Implements the number of reverse-and-add
+/// steps for N to become a palindrome, up to some
+/// limit. Reaching the limit flags N as a likely
+/// Lychrel number. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, ReverseAddSteps, lru_calculate};
+///
+/// let machine = &mut Machine::new(ReverseAddSteps{limit: 50}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 56).expect("56 + 65 = 121"), 1);
+/// assert_eq!(lru_calculate(machine, 59).expect("steps for 59"), 3);
+/// assert_eq!(lru_calculate(machine, 121).expect("already a palindrome"), 0);
+/// assert_eq!(lru_calculate(machine, 196).expect("likely lychrel"), 50);
+/// ```
+#[derive(Debug)]
+pub struct ReverseAddSteps {
+    pub limit: MMInt,
+}
+
+/// Implements the mean of the last `window`
+/// values of some other calculator, up to and
+/// including the Nth value. The window is kept
+/// between calculations, moving on by a single
+/// value where N follows the last N calculated,
+/// and filled anew otherwise.
+///
+/// ```
+/// use math_machines::{Machine, Arithmetic, RollingMean, lru_calculate};
+///
+/// let naturals = Arithmetic{first: 1, step: 1};
+/// let machine  = &mut Machine::new(RollingMean::new(naturals, 3), 128, 50);
+/// let result   = lru_calculate(machine, 5).expect("mean of 4, 5 and 6");
+/// assert_eq!(result.0, 5.0);
+///
+/// let result   = lru_calculate(machine, 6).expect("mean of 5, 6 and 7");
+/// assert_eq!(result.0, 6.0);
+///
+/// let result   = lru_calculate(machine, 0).expect("mean of 1");
+/// assert_eq!(result.0, 1.0);
+///
+/// let result   = lru_calculate(machine, 1).expect("mean of 1 and 2");
+/// assert_eq!(result.0, 1.5);
+/// ```
+#[derive(Debug)]
+pub struct RollingMean<MM> {
+    pub inner: MM,
+    pub window: MMSize,
+    values: RefCell<VecDeque<MMInt>>,
+    last: RefCell<Phase<MMInt, MMInt>>,
+}
+
+/// Implements the ruler sequence, the exponent
+/// of the largest power of 2 dividing N, to
+/// calculate the Nth value. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, RulerSequence, lru_calculate};
+///
+/// let machine = &mut Machine::new(RulerSequence{}, 128, 50);
+/// let result: Vec<_> = (1..9)
+///     .map(|n| lru_calculate(machine, n).expect("Nth ruler"))
+///     .collect();
+/// assert_eq!(result, vec![0, 1, 0, 2, 0, 1, 0, 3]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct RulerSequence;
+
+/// Implements the Stirling numbers of the second
+/// kind, the ways to partition N elements into
+/// K non-empty subsets, to calculate the Nth
+/// value. Rows of the recurrence calculated are
+/// kept as history, resuming from the length of
+/// the history for a new N.
+///
+/// ```
+/// use math_machines::{Machine, Stirling2, lru_calculate};
+///
+/// let machine = &mut Machine::new(Stirling2::new(2), 128, 50);
+/// assert_eq!(lru_calculate(machine, 4).expect("S(4, 2)"), 7);
+/// assert_eq!(lru_calculate(machine, 1).expect("S(1, 2)"), 0);
+///
+/// let machine = &mut Machine::new(Stirling2::new(3), 128, 50);
+/// assert_eq!(lru_calculate(machine, 5).expect("S(5, 3)"), 25);
+/// ```
+#[derive(Debug)]
+pub struct Stirling2 {
+    pub k: MMInt,
+    pub history: RefCell<Vec<Vec<MMInt>>>,
+}
+
+/// Implements the number of ways to write N as
+/// an ordered sum of `k` squares, counting signs
+/// and zeros, to calculate the Nth value. Each N
+/// is calculated independently of any cached
+/// phase. For `k` other than 2, the count is a
+/// convolution over all values up to N.
+///
+/// ```
+/// use math_machines::{Machine, SumOfSquaresReps, lru_calculate};
+///
+/// let machine = &mut Machine::new(SumOfSquaresReps{k: 2}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("r2(1)"), 4);
+/// assert_eq!(lru_calculate(machine, 5).expect("r2(5)"), 8);
+/// assert_eq!(lru_calculate(machine, 3).expect("r2(3)"), 0);
+///
+/// let machine = &mut Machine::new(SumOfSquaresReps{k: 4}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 1).expect("r4(1)"), 8);
+/// assert_eq!(lru_calculate(machine, 10).expect("r4(10)"), 144);
+///
+/// // Both methods agree for k of 2.
+/// let machine = &mut Machine::new(SumOfSquaresReps{k: 2}, 128, 50);
+/// for n in 0..50 {
+///     let reps = SumOfSquaresReps::convolved(2, n).expect("r2(n)");
+///     assert_eq!(lru_calculate(machine, n).expect("r2(n)"), reps);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SumOfSquaresReps {
+    pub k: MMSize,
+}
+
+/// Implements whether N is the sum of two
+/// squares, returning `1` if it is and `0` if
+/// not. Each N is calculated independently of
+/// any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, SumOfTwoSquares, lru_calculate};
+///
+/// let machine = &mut Machine::new(SumOfTwoSquares{}, 128, 50);
+/// assert_eq!(lru_calculate(machine, 5).expect("1 + 4"), 1);
+/// assert_eq!(lru_calculate(machine, 3).expect("not a sum"), 0);
+/// assert_eq!(lru_calculate(machine, 25).expect("9 + 16"), 1);
+/// assert_eq!(lru_calculate(machine, 21).expect("not a sum"), 0);
+/// ```
+#[derive(Debug)]
+pub struct SumOfTwoSquares;
+
+/// Implements the tetrahedral numbers,
+/// `n(n+1)(n+2) / 6`, to calculate the Nth value.
+/// Each N is calculated directly, in closed form.
+///
+/// ```
+/// use math_machines::{Machine, Tetrahedral, lru_calculate};
+///
+/// let machine = &mut Machine::new(Tetrahedral{}, 128, 50);
+/// let result: Vec<_> = [1, 4, 5]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("Nth tetrahedral"))
+///     .collect();
+/// assert_eq!(result, vec![1, 20, 35]);
+/// assert!(lru_calculate(machine, 12_686_161_381_662).is_ok());
+/// assert!(lru_calculate(machine, 12_686_161_381_663).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Tetrahedral;
+
+/// Implements Euler's totient function, the
+/// count of integers up to N coprime to N, to
+/// calculate the Nth value. Each N is calculated
+/// independently of any cached phase.
+///
+/// ```
+/// use math_machines::{Machine, Totient, lru_calculate};
+///
+/// let machine = &mut Machine::new(Totient{}, 128, 50);
+/// let result: Vec<_> = (1..11)
+///     .map(|n| lru_calculate(machine, n).expect("Nth totient"))
+///     .collect();
+/// assert_eq!(result, vec![1, 1, 2, 2, 4, 2, 6, 4, 6, 4]);
+/// assert!(lru_calculate(machine, 0).is_err());
+/// ```
+#[derive(Debug)]
+pub struct Totient;
+
+/// Implements the summatory totient function,
+/// `φ(1) + ... + φ(N)`, to calculate the Nth
+/// value. Results are cached, with lookup in
+/// reverse order, to find the closest value
+/// calculated to a new N, if N does not already
+/// exist.
+///
+/// ```
+/// use math_machines::{Machine, TotientSum, lru_calculate};
+///
+/// let machine = &mut Machine::new(TotientSum{}, 128, 50);
+/// let result: Vec<_> = [1, 5, 10]
+///     .into_iter()
+///     .map(|n| lru_calculate(machine, n).expect("Nth totient sum"))
+///     .collect();
+/// assert_eq!(result, vec![1, 10, 32]);
+/// ```
+#[derive(Debug)]
+pub struct TotientSum;
+
+/// Implements the Ulam sequence to calculate
+/// the Nth value, where `U(0) = 1`, `U(1) = 2`
+/// and each following value is the smallest
+/// integer that is the sum of two distinct
+/// earlier values in exactly one way. Values
+/// calculated are kept as history, resuming
+/// from the length of the history for a new N.
+///
+/// ```
+/// use math_machines::{Machine, Ulam, lru_calculate};
+///
+/// let machine = &mut Machine::new(Ulam::default(), 128, 50);
+/// let result: Vec<_> = (0..10)
+///     .map(|n| lru_calculate(machine, n).expect("Nth ulam"))
+///     .collect();
+/// assert_eq!(result, vec![1, 2, 3, 4, 6, 8, 11, 13, 16, 18]);
+/// ```
+#[derive(Debug, Default)]
+pub struct Ulam {
+    pub history: RefCell<Vec<MMInt>>,
+}
+
+/// Implements the Wedderburn-Etherington
+/// numbers, the count of unordered binary trees
+/// with N leaves, to calculate the Nth value.
+/// Values calculated are kept as history,
+/// resuming from the length of the history for a
+/// new N.
+///
+/// ```
+/// use math_machines::{Machine, WedderburnEtherington, lru_calculate};
+///
+/// let machine = &mut Machine::new(WedderburnEtherington::default(), 128, 50);
+/// let result: Vec<_> = (0..9)
+///     .map(|n| lru_calculate(machine, n).expect("Nth wedderburn-etherington"))
+///     .collect();
+/// assert_eq!(result, vec![0, 1, 1, 1, 2, 3, 6, 11, 23]);
+/// assert!(lru_calculate(machine, 106).is_ok());
+/// assert!(lru_calculate(machine, 107).is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct WedderburnEtherington {
+    pub history: RefCell<Vec<MMInt>>,
+}
+
+impl Calculator<MMInt, MMInt> for AbundantNumbers {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] += 1;
+            while Aliquot::sum_proper_divisors(phase[0]) <= phase[0] {
+                phase[0] += 1;
+            }
+        }
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Aliquot {
+    /// Sum of the proper divisors of an integer.
+    ///
+    /// ```
+    /// use math_machines::Aliquot;
+    ///
+    /// assert_eq!(Aliquot::sum_proper_divisors(12), 16);
+    /// assert_eq!(Aliquot::sum_proper_divisors(28), 28);
+    /// assert_eq!(Aliquot::sum_proper_divisors(1), 0);
+    /// ```
+    pub fn sum_proper_divisors(n: MMInt) -> MMInt {
+        if n <= 1 { return 0; }
+
+        let mut total   = 1;
+        let mut stepper = 2;
+        while stepper * stepper <= n {
+            if n.is_multiple_of(stepper) {
+                total += stepper;
+                if stepper * stepper != n {
+                    total += n / stepper;
+                }
+            }
+            stepper += 1;
+        }
+        total
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Aliquot {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            if phase[0] == 0 { break; }
+            phase[0] = Aliquot::sum_proper_divisors(phase[0]);
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = self.start;
+        phase[1] = self.start;
+    }
+}
+
+impl Calculator<MMInt, MMInt> for AliquotSum {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = Aliquot::sum_proper_divisors(n);
+        Ok(phase.to_owned())
+    }
+}
+
+impl<MM> Calculator<MMFlt, MMInt> for Alternating<MM>
+where
+    MM: Calculator<MMFlt, MMInt, Calculated = Phase<MMFlt, MMInt>>,
+{
+    type Calculated = Phase<MMFlt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        let value = *self.inner.calculate(n, &mut inner)?.result();
+        phase.setinput(n);
+        phase[0] = if n.is_multiple_of(2) { value } else { -value };
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl<I, MM> Calculator<ApproxF64, I> for Approx<MM>
+where
+    I: Clone + Default,
+    MM: Calculator<MMFlt, I, Calculated = Phase<MMFlt, I>>,
+{
+    type Calculated = Phase<ApproxF64, I>;
+    fn calculate(&self, n: I, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut exact = phase.exact();
+        *phase = self.inner.calculate(n, &mut exact)?.approx(self.eps);
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        let mut exact = phase.exact();
+        self.inner.seed(&mut exact);
+        *phase = exact.approx(self.eps);
+    }
+    fn valid_input(&self, n: &I) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Arithmetic {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] = phase[0].checked_add(self.step).ok_or(MachineError::Overflow)?;
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = self.first;
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.step.checked_mul(*n).and_then(|v| v.checked_add(self.first)).is_some()
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<MMInt, MMInt>> {
+        Some(self)
+    }
+}
+
+impl DirectCalculator<MMInt, MMInt> for Arithmetic {
+    fn calculate_direct(&self, n: MMInt) -> MachineResult<MMInt> {
+        self.step
+            .checked_mul(n)
+            .and_then(|v| v.checked_add(self.first))
+            .ok_or(MachineError::Overflow)
+    }
+}
+
+impl<T, I, MM> Calculator<T, I> for Clamp<MM, T>
+where
+    T: Clone + Default + Ord,
+    I: Clone + Default,
+    MM: Calculator<T, I, Calculated = Phase<T, I>>,
+{
+    type Calculated = Phase<T, I>;
+    fn calculate(&self, n: I, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        let value = self.inner.calculate(n.clone(), &mut inner)?.result().to_owned();
+        phase.setinput(n);
+        phase[0] = value.min(self.max.clone());
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &I) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl<F> Debug for ClosureCalculator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClosureCalculator").finish_non_exhaustive()
+    }
+}
+
+impl<F> Calculator<MMInt, MMInt> for ClosureCalculator<F>
+where
+    F: Fn(MMInt) -> MachineResult<MMInt>,
+{
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = (self.f)(n)?;
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<MMInt, MMInt> for CollatzShortcut {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (mut value, mut steps) = (n, 0);
+        phase.setinput(n);
+        while value > 1 {
+            value = if value.is_multiple_of(2) {
+                value / 2
+            } else {
+                value.checked_mul(3)
+                    .and_then(|v| v.checked_add(1))
+                    .ok_or(MachineError::Overflow)? / 2
+            };
+            steps += 1;
+        }
+        phase[0] = steps;
+        Ok(phase.to_owned())
+    }
+}
+
+impl<T, I, A, B> Calculator<T, I> for Compose<A, B>
+where
+    T: Clone + Default,
+    I: Clone + Default,
+    A: Calculator<MMInt, I, Calculated = Phase<MMInt, I>>,
+    B: Calculator<T, MMInt, Calculated = Phase<T, MMInt>>,
+{
+    type Calculated = Phase<T, I>;
+    fn calculate(&self, n: I, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.a.seed(&mut inner);
+        let m = *self.a.calculate(n.clone(), &mut inner)?.result();
+
+        let mut outer = Phase::new();
+        self.b.seed(&mut outer);
+        phase.setinput(n);
+        phase[0] = self.b.calculate(m, &mut outer)?.result().to_owned();
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &I) -> bool {
+        self.a.valid_input(n)
+    }
+}
+
+impl Default for DigitalRoot {
+    fn default() -> Self {
+        Self{base: 10}
+    }
+}
+
+impl Calculator<MMInt, MMInt> for DigitalRoot {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        if self.base < 2 {
+            return Err(MachineError::InvalidInput);
+        }
+        let mut root = n;
+        while root >= self.base {
+            root = digits(root, self.base).iter().sum();
+        }
+        phase.setinput(n);
+        phase[0] = root;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, _n: &MMInt) -> bool {
+        self.base >= 2
+    }
+}
+
+impl<F, G> DirichletConv<F, G>
+where
+    F: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+    G: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    /// Calculate `f(d)g(e)`, each from scratch.
+    fn product(&self, d: MMInt, e: MMInt) -> MachineResult<MMInt> {
+        let (mut fp, mut gp) = (Phase::new(), Phase::new());
+        self.f.seed(&mut fp);
+        self.g.seed(&mut gp);
+        let fd = *self.f.calculate(d, &mut fp)?.result();
+        let ge = *self.g.calculate(e, &mut gp)?.result();
+        fd.checked_mul(ge).ok_or(MachineError::Overflow)
+    }
+}
+
+impl<F, G> Calculator<MMInt, MMInt> for DirichletConv<F, G>
+where
+    F: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+    G: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut total: MMInt = 0;
+        let mut d = 1;
+        while n > 0 && d * d <= n {
+            if n.is_multiple_of(d) {
+                let e = n / d;
+                total = total.checked_add(self.product(d, e)?).ok_or(MachineError::Overflow)?;
+                if d != e {
+                    total = total.checked_add(self.product(e, d)?).ok_or(MachineError::Overflow)?;
+                }
+            }
+            d += 1;
+        }
+        phase.setinput(n);
+        phase[0] = total;
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<MMInt, MMInt> for DivisorCount {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = Primes::factorize(n).iter().map(|(_, e)| e + 1).product();
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Calculator<MMInt, MMInt> for EulerNumberCF {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = match (n, n % 3) {
+            (0, _) => 2,
+            (_, 2) => 2 * (n + 1) / 3,
+            _ => 1,
+        };
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Factorial {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for i in start..stahp {
+            phase[0] = phase[0].checked_mul(i + 1).ok_or(MachineError::Overflow)?;
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = 1;
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // 35! exceeds the bounds of MMInt.
+        *n <= 34
+    }
+}
+
+impl Calculator<MMInt, MMInt> for FactorialTrailingZeros {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = self.calculate_direct(n)?;
+        Ok(phase.to_owned())
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<MMInt, MMInt>> {
+        Some(self)
+    }
+}
+
+impl DirectCalculator<MMInt, MMInt> for FactorialTrailingZeros {
+    fn calculate_direct(&self, n: MMInt) -> MachineResult<MMInt> {
+        let (mut zeros, mut rest) = (0, n);
+        while rest >= 5 {
+            rest /= 5;
+            zeros += rest;
+        }
+        Ok(zeros)
+    }
+}
+
+impl Fibonacci {
+    /// Index N of the given value in the
+    /// Fibonacci sequence, if the value is exactly
+    /// `F(n)`. A value is in the sequence only if
+    /// `5v^2 + 4` or `5v^2 - 4` is a perfect
+    /// square. `1` is given the index `1`.
+    ///
+    /// ```
+    /// use math_machines::Fibonacci;
+    ///
+    /// assert_eq!(Fibonacci::fib_index_of(55), Some(10));
+    /// assert_eq!(Fibonacci::fib_index_of(4), None);
+    /// assert_eq!(Fibonacci::fib_index_of(0), Some(0));
+    /// assert_eq!(Fibonacci::fib_index_of(121393), Some(26));
+    /// ```
+    pub fn fib_index_of(value: MMInt) -> Option<MMInt> {
+        let is_square = |v: MMInt| v.isqrt().pow(2) == v;
+        // Values too large to test are searched
+        // for directly.
+        if let Some(v) = value.checked_mul(value).and_then(|v| v.checked_mul(5)) {
+            let plus  = v.checked_add(4).is_some_and(is_square);
+            let minus = v.checked_sub(4).is_some_and(is_square);
+            if !(plus || minus) { return None; }
+        }
+
+        let (mut n, mut a, mut b): (MMInt, MMInt, MMInt) = (0, 0, 1);
+        while a < value {
+            (a, b) = (b, a.checked_add(b)?);
+            n += 1;
+        }
+        (a == value).then_some(n)
+    }
+    /// Integer from its Zeckendorf representation,
+    /// where the Ith digit marks `F(i+2)`.
+    pub fn from_zeckendorf(digits: &[bool]) -> MMInt {
+        let (mut a, mut b): (MMInt, MMInt) = (1, 2);
+        let mut total = 0;
+        for (i, digit) in digits.iter().enumerate() {
+            if *digit { total += a; }
+            if i + 1 < digits.len() {
+                (a, b) = (b, a + b);
+            }
+        }
+        total
+    }
+    /// Zeckendorf representation of an integer, as
+    /// the sum of non-consecutive Fibonacci values,
+    /// where the Ith digit marks `F(i+2)`.
+    ///
+    /// ```
+    /// use math_machines::Fibonacci;
+    ///
+    /// // 100 = 89 + 8 + 3
+    /// let digits = Fibonacci::zeckendorf(100);
+    /// assert_eq!(digits, vec![false, false, true, false, true, false, false, false, false, true]);
+    /// assert_eq!(Fibonacci::from_zeckendorf(&digits), 100);
+    ///
+    /// for n in 0..1000 {
+    ///     let digits = Fibonacci::zeckendorf(n);
+    ///     assert!(!digits.windows(2).any(|w| w[0] && w[1]));
+    ///     assert_eq!(Fibonacci::from_zeckendorf(&digits), n);
+    /// }
+    /// ```
+    pub fn zeckendorf(mut n: MMInt) -> Vec<bool> {
+        let mut fibs: Vec<MMInt> = vec![1, 2];
+        while let Some(next) = fibs[fibs.len() - 2].checked_add(fibs[fibs.len() - 1]) {
+            if next > n { break; }
+            fibs.push(next);
+        }
+
+        let mut digits = vec![false; fibs.len()];
+        for (i, f) in fibs.iter().enumerate().rev() {
+            if *f <= n {
+                digits[i] = true;
+                n -= f;
+            }
+        }
+        while digits.last() == Some(&false) {
+            digits.pop();
+        }
+        digits
+    }
+    /// Add two integers through their Zeckendorf
+    /// representations, normalizing carries so no
+    /// two consecutive Fibonacci values are used.
+    ///
+    /// ```
+    /// use math_machines::Fibonacci;
+    ///
+    /// for (a, b) in [(0, 0), (1, 1), (4, 7), (12, 33), (100, 100), (987, 1597)] {
+    ///     assert_eq!(Fibonacci::zeckendorf_add(a, b), a + b);
+    /// }
+    /// for (a, b) in (0..60).flat_map(|a| (0..60).map(move |b| (a, b))) {
+    ///     assert_eq!(Fibonacci::zeckendorf_add(a, b), a + b);
+    /// }
+    /// ```
+    pub fn zeckendorf_add(a: MMInt, b: MMInt) -> MMInt {
+        let (a, b) = (Fibonacci::zeckendorf(a), Fibonacci::zeckendorf(b));
+        let mut digits: Vec<MMInt> = vec![0; a.len().max(b.len())];
+        for (i, d) in a.iter().enumerate() { digits[i] += *d as MMInt; }
+        for (i, d) in b.iter().enumerate() { digits[i] += *d as MMInt; }
+
+        // Normalize, where 2F(k) = F(k+1) + F(k-2)
+        // and F(k) + F(k+1) = F(k+2).
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..digits.len() {
+                if i + 2 >= digits.len() {
+                    digits.push(0);
+                }
+                if digits[i] >= 2 {
+                    digits[i] -= 2;
+                    digits[i + 1] += 1;
+                    match i {
+                        0 => {},
+                        1 => digits[0] += 1,
+                        _ => digits[i - 2] += 1,
+                    }
+                    changed = true;
+                } else if digits[i] >= 1 && digits[i + 1] >= 1 {
+                    digits[i] -= 1;
+                    digits[i + 1] -= 1;
+                    digits[i + 2] += 1;
+                    changed = true;
+                }
+            }
+        }
+        let digits: Vec<bool> = digits.iter().map(|d| *d == 1).collect();
+        debug_assert!(!digits.windows(2).any(|w| w[0] && w[1]), "consecutive fibonacci values");
+        Fibonacci::from_zeckendorf(&digits)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Fibonacci {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (&mut phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in *start..stahp {
+            advance_linear(phase, &[1, 1])?;
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        *phase.prev_mut() = 1;
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // F(187) exceeds the bounds of MMInt.
+        *n <= 186
+    }
+    fn as_modular(&self) -> Option<&dyn ModularCalculator<MMInt>> {
+        Some(self)
+    }
+}
+
+impl ModularCalculator<MMInt> for Fibonacci {
+    fn calculate_mod(&self, n: MMInt, modulus: MMInt) -> MachineResult<MMInt> {
+        if modulus == 0 {
+            return Err(MachineError::DivisionByZero);
+        }
+        let calc  = FibonacciMod{modulus, fast: true};
+        let phase = &mut Phase::new();
+        calc.seed(phase);
+        Ok(*calc.calculate(n, phase)?.result())
+    }
+}
+
+impl Calculator<MMInt, MMInt> for FibonacciDigitCount {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = self.calculate_direct(n)?;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // Past N of 10^8 the closed form loses
+        // precision in f64, first miscounting at
+        // N of 279369365.
+        *n <= 100_000_000
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<MMInt, MMInt>> {
+        Some(self)
+    }
+}
+
+impl DirectCalculator<MMInt, MMInt> for FibonacciDigitCount {
+    fn calculate_direct(&self, n: MMInt) -> MachineResult<MMInt> {
+        if !self.valid_input(&n) {
+            return Err(MachineError::InvalidInput);
+        }
+        // F(n) is exact within MMInt up to F(186),
+        // so its digits are counted directly.
+        let fibonacci = Fibonacci{};
+        if fibonacci.valid_input(&n) {
+            let phase = &mut Phase::new();
+            fibonacci.seed(phase);
+            let value = *fibonacci.calculate(n, phase)?.result();
+            return Ok(value.checked_ilog10().map_or(1, |d| d as MMInt + 1));
+        }
+        let phi   = (1.0 + 5f64.sqrt()) / 2.0;
+        let count = n as f64 * phi.log10() - 5f64.sqrt().log10();
+        Ok(count.floor() as MMInt + 1)
+    }
+}
+
+impl FibonacciMod {
+    /// Pisano period of the modulus, the period
+    /// of the Fibonacci sequence reduced by it.
+    /// Errors for a modulus of 0.
+    ///
+    /// ```
+    /// use math_machines::FibonacciMod;
+    ///
+    /// let periods: Vec<_> = [1, 2, 10, 97]
+    ///     .into_iter()
+    ///     .map(|m| FibonacciMod::pisano_period(m).expect("pisano period"))
+    ///     .collect();
+    /// assert_eq!(periods, vec![1, 3, 60, 196]);
+    /// assert!(FibonacciMod::pisano_period(0).is_err());
+    /// ```
+    pub fn pisano_period(modulus: MMInt) -> MachineResult<MMInt> {
+        if modulus == 0 {
+            return Err(MachineError::DivisionByZero);
+        }
+        let (mut a, mut b, mut period) = (0, 1 % modulus, 0);
+        loop {
+            (a, b) = (b, (a + b) % modulus);
+            period += 1;
+            if a == 0 && b == 1 % modulus {
+                return Ok(period);
+            }
+        }
+    }
+}
+
+impl Calculator<MMInt, MMInt> for FibonacciMod {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        if self.modulus == 0 {
+            return Err(MachineError::DivisionByZero);
+        }
+        let (mut start, mut stahp) = (phase.input().to_owned(), n);
+        // The period is at most 6 times the
+        // modulus, so finding it only pays off for
+        // N further than that from the phase.
+        if self.fast && (stahp - start) / 6 > self.modulus {
+            // Values repeat every period, so restart
+            // from the seed at N within the 1st one.
+            let period = FibonacciMod::pisano_period(self.modulus)?;
+            *phase = Phase::new();
+            self.seed(phase);
+            (start, stahp) = (0, n % period);
+        }
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase.rotate(1);
+            phase[0] = if phase[1] >= self.modulus - phase[2] {
+                phase[1] - (self.modulus - phase[2])
+            } else {
+                phase[1] + phase[2]
+            };
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[1] = 1 % self.modulus.max(1);
+    }
+    fn valid_input(&self, _n: &MMInt) -> bool {
+        self.modulus > 0
+    }
+}
+
+impl<MM, T> FullMemo<MM, T> {
+    /// Create a new instance of `FullMemo` with
+    /// nothing memoized.
+    pub fn new(inner: MM) -> Self {
+        Self{inner, memo: RefCell::new(HashMap::new())}
+    }
+}
+
+impl<T, MM> Calculator<T, MMInt> for FullMemo<MM, T>
+where
+    T: Clone,
+    MM: Calculator<T, MMInt, Calculated = Phase<T, MMInt>>,
+{
+    type Calculated = Phase<T, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        if let Some(memoized) = self.memo.borrow().get(&n) {
+            *phase = memoized.clone();
+            return Ok(phase.to_owned());
+        }
+        let calc = self.inner.calculate(n, phase)?;
+        self.memo.borrow_mut().insert(n, calc.clone());
+        Ok(calc)
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        self.inner.seed(phase);
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for GaussianNorm {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] += 1;
+            while !SumOfTwoSquares::is_sum_of_two_squares(phase[0]) {
+                phase[0] += 1;
+            }
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl GcdWith {
+    /// Greatest common divisor of two integers,
+    /// by the Euclidean algorithm.
+    ///
+    /// ```
+    /// use math_machines::GcdWith;
+    ///
+    /// assert_eq!(GcdWith::gcd(48, 18), 6);
+    /// assert_eq!(GcdWith::gcd(0, 5), 5);
+    /// ```
+    pub fn gcd(mut a: MMInt, mut b: MMInt) -> MMInt {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+}
+
+impl Calculator<MMInt, MMInt> for GcdWith {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = GcdWith::gcd(n, self.m);
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<MMFlt, MMInt> for GoldenRatio {
+    type Calculated = Phase<MMFlt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] = OrderedFloat(1.0 + 1.0/phase[0].0);
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = OrderedFloat(1.0);
+    }
+}
+
+impl Calculator<MMInt, MMInt> for GreatestPrimeFactor {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = Primes::factorize(n).last().map_or(1, |(p, _)| *p);
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Harmonic {
+    /// Approximate the harmonic number of a real
+    /// argument, where `H(x) = digamma(x+1) + γ`.
+    /// Agrees with the Nth value of the sequence
+    /// for integer arguments. Errors for arguments
+    /// which are not finite, or are negative
+    /// integers, where `H(x)` has its poles.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Harmonic, lru_calculate};
+    /// use ordered_float::OrderedFloat;
+    ///
+    /// let machine = &mut Machine::new(Harmonic{}, 128, 50);
+    /// let result  = lru_calculate(machine, 5).expect("5th harmonic");
+    /// let approx  = Harmonic::harmonic_at(OrderedFloat(5.0)).expect("H(5)");
+    /// assert!((approx - result).abs() < 1e-12);
+    ///
+    /// // H(1/2) = 2 - 2ln(2), as is H(-3/2).
+    /// for x in [0.5, -1.5] {
+    ///     let result = Harmonic::harmonic_at(OrderedFloat(x)).expect("H(x)");
+    ///     assert!((result.0 - (2.0 - 2.0 * 2f64.ln())).abs() < 1e-12);
+    /// }
+    /// // H(-1/2) = -2ln(2).
+    /// let result = Harmonic::harmonic_at(OrderedFloat(-0.5)).expect("H(-1/2)");
+    /// assert!((result.0 + 2.0 * 2f64.ln()).abs() < 1e-12);
+    /// assert!(Harmonic::harmonic_at(OrderedFloat(-1e12 - 0.5)).is_ok());
+    ///
+    /// for x in [f64::NEG_INFINITY, f64::INFINITY, f64::NAN, -1.0, -4.0] {
+    ///     assert!(Harmonic::harmonic_at(OrderedFloat(x)).is_err());
+    /// }
+    /// ```
+    pub fn harmonic_at(x: MMFlt) -> MachineResult<MMFlt> {
+        const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+        let x = x.0;
+        if !x.is_finite() || (x < 0.0 && x.fract() == 0.0) {
+            return Err(MachineError::InvalidInput);
+        }
+        // Reflect negative arguments past -1, where
+        // H(x) = H(-x-1) - π cot(πx), rather than
+        // shift up from them.
+        if x < -1.0 {
+            let pi_x = std::f64::consts::PI * x;
+            let cot  = pi_x.cos() / pi_x.sin();
+            let reflected = Harmonic::harmonic_at(OrderedFloat(-x - 1.0))?;
+            return Ok(reflected - std::f64::consts::PI * cot);
+        }
+
+        // Shift the argument of digamma up, where
+        // digamma(y) = digamma(y+1) - 1/y, until
+        // the asymptotic series is accurate.
+        let (mut y, mut shift) = (x + 1.0, 0.0);
+        while y < 10.0 {
+            shift -= 1.0 / y;
+            y += 1.0;
+        }
+        let inv2 = 1.0 / (y * y);
+        let series = inv2 * (1.0/12.0 - inv2 * (1.0/120.0 - inv2 * (1.0/252.0 - inv2 * (1.0/240.0 - inv2/132.0))));
+        let digamma = y.ln() - 0.5 / y - series + shift;
+        Ok(OrderedFloat(digamma + EULER_GAMMA))
+    }
+}
+
+impl Calculator<MMFlt, MMInt> for Harmonic {
+    type Calculated = Phase<MMFlt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (&mut phase.input().to_owned(), n);
+        phase.setinput(n);
+        // The count of terms summed so far is
+        // kept as the 1st value of the phase.
+        for _ in *start..stahp {
+            *phase.prev_mut() += 1.0;
+            let term = 1.0 / phase.prev().0;
+            *phase.result_mut() += term;
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<(MMFlt, MMFlt), MMInt> for HarmonicInterval {
+    type Calculated = Phase<(MMFlt, MMFlt), MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase.prev_mut().0.0 += 1.0;
+            let term = 1.0 / phase.prev().0.0;
+            let (lower, upper) = *phase.result();
+            *phase.result_mut() = (
+                OrderedFloat((lower.0 + term.next_down()).next_down()),
+                OrderedFloat((upper.0 + term.next_up()).next_up()),
+            );
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<(MMInt, MMInt), MMInt> for HarmonicExact {
+    type Calculated = Phase<(MMInt, MMInt), MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for k in start..stahp {
+            // p/q + 1/m = (pm + q)/qm
+            let ((p, q), m) = (phase[0], k + 1);
+            let numer = p.checked_mul(m).and_then(|v| v.checked_add(q));
+            let denom = q.checked_mul(m);
+            let (numer, denom) = numer.zip(denom).ok_or(MachineError::Overflow)?;
+            let divisor = GcdWith::gcd(numer, denom);
+            phase[0] = (numer / divisor, denom / divisor);
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = (0, 1);
+    }
+}
+
+impl Hexagonal {
+    /// Integer is a hexagonal number or not,
+    /// where `x = n(2n-1)` only if
+    /// `n = (1 + sqrt(1 + 8x)) / 4` is an integer.
+    ///
+    /// ```
+    /// use math_machines::Hexagonal;
+    ///
+    /// assert_eq!(Hexagonal::is_hexagonal(45), true);
+    /// assert_eq!(Hexagonal::is_hexagonal(46), false);
+    /// assert_eq!(Hexagonal::is_hexagonal(0), true);
+    /// ```
+    pub fn is_hexagonal(x: MMInt) -> bool {
+        if x == 0 { return true; }
+        let Some(d) = x.checked_mul(8).and_then(|v| v.checked_add(1)) else {
+            return false;
+        };
+        let root = d.isqrt();
+        root * root == d && (root + 1).is_multiple_of(4)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Hexagonal {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = self.calculate_direct(n)?;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // Values past N of 13043817825332782212 exceed
+        // the bounds of MMInt.
+        *n <= 13_043_817_825_332_782_212
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<MMInt, MMInt>> {
+        Some(self)
+    }
+}
+
+impl DirectCalculator<MMInt, MMInt> for Hexagonal {
+    fn calculate_direct(&self, n: MMInt) -> MachineResult<MMInt> {
+        match n {
+            0 => Ok(0),
+            _ => n.checked_mul(2)
+                .and_then(|v| (v - 1).checked_mul(n))
+                .ok_or(MachineError::Overflow),
+        }
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Hyperfactorial {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for k in (start + 1)..=stahp {
+            phase[0] = u32::try_from(k)
+                .ok()
+                .and_then(|e| k.checked_pow(e))
+                .and_then(|p| phase[0].checked_mul(p))
+                .ok_or(MachineError::Overflow)?;
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = 1;
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // H(10) exceeds the bounds of MMInt.
+        *n <= 9
+    }
+}
+
+impl<MM> Instrumented<MM> {
+    /// Phase of the inner calculator, without
+    /// the iterations counted.
+    fn inner_phase<T: Clone + Default>(phase: &Phase<Counted<T>, MMInt>) -> Phase<T, MMInt> {
+        let values = phase.phase().clone().map(|c| c.value);
+        Phase::from_slice(&values, *phase.input()).expect("inner phase")
+    }
+    /// Phase of the instrumented calculator, with
+    /// the iterations counted set on the result.
+    fn outer_phase<T: Clone + Default>(phase: &Phase<T, MMInt>, steps: MMSize) -> Phase<Counted<T>, MMInt> {
+        let mut values = phase.phase().clone().map(|value| Counted{value, steps: 0});
+        values[0].steps = steps;
+        Phase::from_slice(&values, *phase.input()).expect("outer phase")
+    }
+}
+
+impl<T, MM> Calculator<Counted<T>, MMInt> for Instrumented<MM>
+where
+    T: Clone + Default,
+    MM: CountingCalculator<T, MMInt, Calculated = Phase<T, MMInt>>,
+{
+    type Calculated = Phase<Counted<T>, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let inner = &mut Instrumented::<MM>::inner_phase(phase);
+        let (calc, steps) = self.inner.calculate_counted(n, inner)?;
+        *phase = Instrumented::<MM>::outer_phase(&calc, phase.result().steps + steps);
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        let inner = &mut Instrumented::<MM>::inner_phase(phase);
+        self.inner.seed(inner);
+        *phase = Instrumented::<MM>::outer_phase(inner, 0);
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Keith {
+    /// Integer is a Keith number or not.
+    ///
+    /// ```
+    /// use math_machines::Keith;
+    ///
+    /// assert_eq!(Keith::is_keith(197), true);
+    /// assert_eq!(Keith::is_keith(198), false);
+    /// assert_eq!(Keith::is_keith(7), false);
+    /// ```
+    pub fn is_keith(n: MMInt) -> bool {
+        if n < 10 { return false; }
+
+        let mut terms = vec![];
+        let mut rest  = n;
+        while rest > 0 {
+            terms.insert(0, rest % 10);
+            rest /= 10;
+        }
+
+        let width = terms.len();
+        let mut next = terms.iter().sum::<MMInt>();
+        while next < n {
+            terms.push(next);
+            next = terms[terms.len() - width..].iter().sum();
+        }
+        next == n
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Keith {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] += 1;
+            while !Keith::is_keith(phase[0]) {
+                phase[0] += 1;
+            }
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = 14;
+    }
+}
+
+impl Calculator<MMInt, MMInt> for LeastPrimeFactor {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        // Stop trial division at the first factor,
+        // rather than factorizing N completely.
+        let mut stepper = 2;
+        while stepper * stepper <= n && !n.is_multiple_of(stepper) {
+            stepper += if stepper == 2 { 1 } else { 2 };
+        }
+        phase.setinput(n);
+        phase[0] = if stepper * stepper <= n { stepper } else { n };
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Leonardo {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for i in start..stahp {
+            phase.rotate(1);
+            phase[0] = if i == 0 {
+                1
+            } else {
+                phase[1].checked_add(phase[2])
+                    .and_then(|v| v.checked_add(1))
+                    .ok_or(MachineError::Overflow)?
+            };
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = 1;
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // L(184) exceeds the bounds of MMInt.
+        *n <= 183
+    }
+}
+
+impl Calculator<MMSInt, MMInt> for Liouville {
+    type Calculated = Phase<MMSInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let omega: MMInt = Primes::factorize(n).iter().map(|(_, e)| e).sum();
+        phase.setinput(n);
+        phase[0] = if omega.is_multiple_of(2) { 1 } else { -1 };
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Lucas {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for i in start..stahp {
+            phase.rotate(1);
+            phase[0] = if i == 0 { 1 } else { phase[1] + phase[2] };
+        }
+        Ok(phase.to_owned())
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        phase[0] = 2;
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // L(185) exceeds the bounds of MMInt.
+        *n <= 184
+    }
+}
+
+impl Calculator<MMSInt, MMInt> for LucasSequence {
+    type Calculated = Phase<MMSInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for i in start..stahp {
+            phase.rotate(1);
+            phase[0] = if i == 0 {
+                1
+            } else {
+                self.p.checked_mul(phase[1])
+                    .zip(self.q.checked_mul(phase[2]))
+                    .and_then(|(a, b)| a.checked_sub(b))
+                    .ok_or(MachineError::Overflow)?
+            };
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl Palindrome {
+    /// Get the next sequential palindromic
+    /// number after N, if it fits within
+    /// `MMInt`, by mirroring the leading half of
+    /// its digits, incrementing the half where
+    /// the mirror is not past N.
+    ///
+    /// ```
+    /// use math_machines::Palindrome;
+    ///
+    /// assert_eq!(Palindrome::next_palindrome(0), Some(1));
+    /// assert_eq!(Palindrome::next_palindrome(9), Some(11));
+    /// assert_eq!(Palindrome::next_palindrome(1221), Some(1331));
+    /// assert_eq!(Palindrome::next_palindrome(999), Some(1001));
+    /// assert_eq!(Palindrome::next_palindrome(10), Some(11));
+    /// assert_eq!(Palindrome::next_palindrome(130), Some(131));
+    /// assert_eq!(Palindrome::next_palindrome(1300), Some(1331));
+    /// assert_eq!(Palindrome::next_palindrome(1399), Some(1441));
+    /// assert_eq!(Palindrome::next_palindrome(u128::MAX), None);
+    /// ```
+    pub fn next_palindrome(n: MMInt) -> Option<MMInt> {
+        let width = n.checked_ilog10().unwrap_or(0) + 1;
+        let half  = n / 10u128.pow(width / 2);
+        let candidate = Palindrome::mirror(half, width)?;
+        if candidate > n {
+            return Some(candidate);
+        }
+        // Carrying into a new digit, as with 99,
+        // gives the first palindrome of the next
+        // width.
+        if half + 1 == 10u128.pow(width.div_ceil(2)) {
+            return 10u128.checked_pow(width)?.checked_add(1);
+        }
+        Palindrome::mirror(half + 1, width)
+    }
+    /// Palindrome of the given width, with the
+    /// given leading half of its digits.
+    fn mirror(half: MMInt, width: u32) -> Option<MMInt> {
+        let mut result = half;
+        let mut rest   = if width % 2 == 1 { half / 10 } else { half };
+        while rest > 0 {
+            result = result.checked_mul(10)?.checked_add(rest % 10)?;
+            rest /= 10;
+        }
+        Some(result)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Palindrome {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] = Palindrome::next_palindrome(phase[0]).ok_or(MachineError::Overflow)?;
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl PiSpigot {
+    /// Create a new spigot, exact for the given
+    /// count of digits.
+    fn new(capacity: MMSize) -> Self {
+        Self{
+            remainders: vec![2; capacity * 10 / 3 + 1],
+            capacity,
+            ..Self::default()
+        }
+    }
+    /// Take one step of the spigot of Rabinowitz
+    /// and Wagon, adding digits it releases to the
+    /// history where the history does not already
+    /// have them.
+    fn step(&mut self, history: &mut Vec<MMInt>) {
+        let mut carry = 0;
+        for i in (1..=self.remainders.len()).rev() {
+            let x = 10 * self.remainders[i - 1] + carry * i as MMInt;
+            let denominator = 2 * i as MMInt - 1;
+            self.remainders[i - 1] = x % denominator;
+            carry = x / denominator;
+        }
+        self.remainders[0] = carry % 10;
+        self.steps += 1;
+
+        let (released, predigit) = match carry / 10 {
+            9 => {
+                self.nines += 1;
+                return;
+            },
+            10 => ((self.predigit + 1, 0), 0),
+            q  => ((self.predigit, 9), q),
+        };
+        let nines = std::mem::take(&mut self.nines);
+        self.predigit = predigit;
+        for digit in std::iter::once(released.0).chain(std::iter::repeat_n(released.1, nines)) {
+            self.released += 1;
+            // The 1st digit released is the leading
+            // zero, rather than a digit of Pi.
+            if self.released > history.len() + 1 {
+                history.push(digit);
+            }
+        }
+    }
+}
+
+impl Calculator<MMInt, MMInt> for PiDigits {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let history = &mut self.history.borrow_mut();
+        let spigot  = &mut self.spigot.borrow_mut();
+        let wanted  = n as MMSize + 1;
+        while history.len() < wanted {
+            if spigot.steps >= spigot.capacity {
+                // Size the spigot a few digits beyond
+                // what is wanted, as trailing digits
+                // may be held back as a run of nines.
+                **spigot = PiSpigot::new(wanted.max(spigot.capacity * 2) + 10);
+            }
+            spigot.step(history);
+        }
+        phase.setinput(n);
+        phase[0] = history[n as MMSize];
+        Ok(phase.to_owned())
+    }
+}
+
+impl<MM, F> Debug for Map<MM, F>
+where
+    MM: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<MM, F> Calculator<MMInt, MMInt> for Map<MM, F>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+    F: Fn(MMInt) -> MMInt,
+{
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        phase.setinput(n);
+        phase[0] = (self.f)(*self.inner.calculate(n, &mut inner)?.result());
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Calculator<MMSInt, MMInt> for Mertens {
+    type Calculated = Phase<MMSInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for k in (start + 1)..=stahp {
+            phase[0] += Mobius::mobius(k);
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl Mobius {
+    /// Möbius function of an integer.
+    ///
+    /// ```
+    /// use math_machines::Mobius;
+    ///
+    /// assert_eq!(Mobius::mobius(1), 1);
+    /// assert_eq!(Mobius::mobius(12), 0);
+    /// assert_eq!(Mobius::mobius(105), -1);
+    /// ```
+    pub fn mobius(n: MMInt) -> MMSInt {
+        let factors = Primes::factorize(n);
+        if factors.iter().any(|(_, e)| *e > 1) {
+            0
+        } else if factors.len().is_multiple_of(2) {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+impl Calculator<MMSInt, MMInt> for Mobius {
+    type Calculated = Phase<MMSInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = Mobius::mobius(n);
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl<MM> Calculator<MMInt, MMInt> for Mod<MM>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        phase.setinput(n);
+        phase[0] = self.inner
+            .calculate(n, &mut inner)?
+            .result()
+            .checked_rem(self.modulus)
+            .ok_or(MachineError::DivisionByZero)?;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.modulus > 0 && self.inner.valid_input(n)
+    }
+}
+
+impl Narcissistic {
+    /// Integer is a narcissistic number or not,
+    /// in the given base.
+    ///
+    /// Panics if `base` is less than 2.
+    ///
+    /// ```
+    /// use math_machines::Narcissistic;
+    ///
+    /// assert_eq!(Narcissistic::is_narcissistic(153, 10), true);
+    /// assert_eq!(Narcissistic::is_narcissistic(154, 10), false);
+    /// assert_eq!(Narcissistic::is_narcissistic(9474, 10), true);
+    /// assert_eq!(Narcissistic::is_narcissistic(17, 3), true);
+    /// ```
+    pub fn is_narcissistic(n: MMInt, base: MMInt) -> bool {
+        let digits = digits(n, base);
+        let width  = digits.len() as u32;
+        let total  = digits
+            .iter()
+            .try_fold(0 as MMInt, |acc, d| d.checked_pow(width).and_then(|p| acc.checked_add(p)));
+        total == Some(n)
+    }
+}
+
+impl Default for Narcissistic {
+    fn default() -> Self {
+        Self{base: 10}
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Narcissistic {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        if self.base < 2 {
+            return Err(MachineError::InvalidInput);
+        }
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] += 1;
+            while !Narcissistic::is_narcissistic(phase[0], self.base) {
+                phase[0] += 1;
+            }
+        }
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, _n: &MMInt) -> bool {
+        self.base >= 2
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Polygonal {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = self.calculate_direct(n)?;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, _n: &MMInt) -> bool {
+        self.sides >= 3
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<MMInt, MMInt>> {
+        Some(self)
+    }
+}
+
+impl DirectCalculator<MMInt, MMInt> for Polygonal {
+    fn calculate_direct(&self, n: MMInt) -> MachineResult<MMInt> {
+        // ((s-2)n^2 - (s-4)n) / 2 = (s-2)n(n-1)/2 + n
+        n.checked_mul(n.saturating_sub(1))
+            .and_then(|v| (v / 2).checked_mul(self.sides - 2))
+            .and_then(|v| v.checked_add(n))
+            .ok_or(MachineError::Overflow)
+    }
+}
+
+impl Primes {
+    /// Prime factors of an integer, paired with
+    /// their exponents, in ascending order.
+    ///
+    /// ```
+    /// use math_machines::Primes;
+    ///
+    /// assert_eq!(Primes::factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+    /// assert_eq!(Primes::factorize(97), vec![(97, 1)]);
+    /// assert_eq!(Primes::factorize(1), vec![]);
+    /// ```
+    pub fn factorize(mut n: MMInt) -> Vec<(MMInt, MMInt)> {
+        let mut factors = vec![];
+        let mut stepper = 2;
+        while n > 1 && stepper * stepper <= n {
+            let mut exponent = 0;
+            while n.is_multiple_of(stepper) {
+                n /= stepper;
+                exponent += 1;
+            }
+            if exponent > 0 {
+                factors.push((stepper, exponent));
+            }
+            stepper += if stepper == 2 { 1 } else { 2 };
+        }
+        if n > 1 {
+            factors.push((n, 1));
+        }
+        factors
+    }
+    /// Integer is a prime number or not.
+    ///
+    /// ```
+    /// use math_machines::Primes;
+    ///
+    /// assert_eq!(Primes::is_prime(98), false);
+    /// assert_eq!(Primes::is_prime(144), false);
+    /// assert_eq!(Primes::is_prime(181), true);
+    /// ```
+    pub fn is_prime(n: MMInt) -> bool {
+        if n <= 1 { return false; }
+        if n <= 3 { return true; }
+        if n.is_multiple_of(2) || n.is_multiple_of(3) { return false; }
+
+        let mut stepper: MMInt = 5;
+        while stepper.pow(2) <= n {
+            if n.is_multiple_of(stepper) || n.is_multiple_of(stepper + 2) {
+                return false;
+            }
+            stepper += 6;
+        }
+        true
+    }
+    /// Integer is a prime number or not, by a
+    /// deterministic Miller-Rabin test. Witnesses
+    /// are valid for N below `3.3 * 10^24`,
+    /// falling back to `is_prime` for larger N.
+    ///
+    /// ```
+    /// use math_machines::Primes;
+    ///
+    /// assert_eq!(Primes::is_prime_mr(1_000_000_007), true);
+    /// assert_eq!(Primes::is_prime_mr(1_000_000_005), false);
+    /// assert_eq!(Primes::is_prime_mr(18_446_744_073_709_551_557), true);
+    /// assert_eq!(Primes::is_prime_mr(11_111_111_111_111_111_111_111), true);
+    /// assert_eq!(Primes::is_prime_mr(18_446_744_073_709_551_557 * 3), false);
+    /// for n in 2..10000 {
+    ///     assert_eq!(Primes::is_prime_mr(n), Primes::is_prime(n));
+    /// }
+    ///
+    /// // Carmichael numbers.
+    /// for n in [561, 41_041, 825_265, 321_197_185] {
+    ///     assert_eq!(Primes::is_prime_mr(n), false);
+    /// }
+    /// // Strong pseudoprimes to every prime base up
+    /// // to 23, and up to 37, caught by witnesses 29
+    /// // and 41 respectively.
+    /// assert_eq!(Primes::is_prime_mr(3_825_123_056_546_413_051), false);
+    /// assert_eq!(Primes::is_prime_mr(318_665_857_834_031_151_167_461), false);
+    /// // Primes and composites past u64, squared by
+    /// // doubling.
+    /// assert_eq!(Primes::is_prime_mr(18_446_744_073_709_551_629), true);
+    /// assert_eq!(Primes::is_prime_mr(1_208_925_819_614_629_174_706_189), true);
+    /// assert_eq!(Primes::is_prime_mr(4_294_967_311 * 1_099_511_627_791), false);
+    /// ```
+    pub fn is_prime_mr(n: MMInt) -> bool {
+        const WITNESSES: [MMInt; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+        const WITNESSES_BOUND: MMInt = 3_317_044_064_679_887_385_961_981;
+        if n >= WITNESSES_BOUND { return Primes::is_prime(n); }
+        if n < 2 { return false; }
+        if let Some(w) = WITNESSES.iter().find(|w| n.is_multiple_of(**w)) {
+            return n == *w;
+        }
+
+        let pow_mod = |mut base: MMInt, mut exp: MMInt| {
+            let mut result = 1;
+            while exp > 0 {
+                if exp & 1 == 1 { result = Primes::mul_mod(result, base, n); }
+                base = Primes::mul_mod(base, base, n);
+                exp >>= 1;
+            }
+            result
+        };
+
+        let (mut d, mut r) = (n - 1, 0);
+        while d.is_multiple_of(2) {
+            (d, r) = (d / 2, r + 1);
+        }
+        WITNESSES.iter().all(|w| {
+            let mut x = pow_mod(*w, d);
+            if x == 1 || x == n - 1 { return true; }
+            for _ in 1..r {
+                x = Primes::mul_mod(x, x, n);
+                if x == n - 1 { return true; }
+            }
+            false
+        })
+    }
+    /// Integer is a prime number or not, by
+    /// Wilson's theorem, where `(n-1)! = -1 mod n`
+    /// for primes only. Only practical for small
+    /// N, as the factorial takes N steps, though
+    /// it does not overflow for any N.
+    ///
+    /// ```
+    /// use math_machines::Primes;
+    ///
+    /// for n in 2..50 {
+    ///     assert_eq!(Primes::is_prime_wilson(n), Primes::is_prime(n));
+    /// }
+    /// assert_eq!(Primes::is_prime_wilson(0), false);
+    /// assert_eq!(Primes::is_prime_wilson(1), false);
+    /// ```
+    pub fn is_prime_wilson(n: MMInt) -> bool {
+        if n <= 1 { return false; }
+        let factorial = (2..n).fold(1 % n, |acc, k| Primes::mul_mod(acc, k, n));
+        factorial == n - 1
+    }
+    /// All prime numbers within `lo..=hi`, by a
+    /// segmented sieve. Base primes up to the
+    /// square root of `hi` are sieved first, and
+    /// then used to sieve the range itself.
+    ///
+    /// ```
+    /// use math_machines::Primes;
+    ///
+    /// assert_eq!(Primes::primes_in_range(10, 30), vec![11, 13, 17, 19, 23, 29]);
+    /// assert_eq!(Primes::primes_in_range(0, 10), vec![2, 3, 5, 7]);
+    /// assert_eq!(Primes::primes_in_range(114, 126), vec![]);
+    /// assert_eq!(Primes::primes_in_range(30, 10), vec![]);
+    /// ```
+    pub fn primes_in_range(lo: MMInt, hi: MMInt) -> Vec<MMInt> {
+        let lo = lo.max(2);
+        if lo > hi { return vec![]; }
+
+        let root = hi.isqrt() as MMSize;
+        let mut base = vec![true; root + 1];
+        let mut base_primes = vec![];
+        for i in 2..=root {
+            if !base[i] { continue; }
+            base_primes.push(i as MMInt);
+            (i * i..=root).step_by(i).for_each(|j| base[j] = false);
+        }
+
+        let mut segment = vec![true; (hi - lo + 1) as MMSize];
+        for p in base_primes {
+            let first = (p * p).max(lo.div_ceil(p) * p);
+            (first..=hi).step_by(p as MMSize).for_each(|j| segment[(j - lo) as MMSize] = false);
+        }
+        segment
+            .iter()
+            .enumerate()
+            .filter(|(_, prime)| **prime)
+            .map(|(i, _)| lo + i as MMInt)
+            .collect()
+    }
+    /// Get the next sequential prime number.
+    ///
+    /// ```
+    /// use math_machines::Primes;
+    ///
+    /// assert_eq!(Primes::next_prime(3517), 3527);
+    /// assert_eq!(Primes::next_prime(7489), 7499);
+    /// ```
+    pub fn next_prime(n: MMInt) -> MMInt {
+        Primes::next_prime_counted(n).0
+    }
+    /// Product of values below N, reduced by N.
+    /// Products fit within MMInt only while N fits
+    /// within u64, otherwise multiply by doubling,
+    /// where sums of values below N still fit.
+    fn mul_mod(mut a: MMInt, mut b: MMInt, n: MMInt) -> MMInt {
+        if n <= u64::MAX as MMInt { return a * b % n; }
+        let mut result = 0;
+        while b > 0 {
+            if b & 1 == 1 { result = (result + a) % n; }
+            a = (a + a) % n;
+            b >>= 1;
+        }
+        result
+    }
+    /// Next sequential prime, with the number of
+    /// candidates considered to find it.
+    fn next_prime_counted(mut n: MMInt) -> (MMInt, MMSize) {
+        if n == 0 { return (2, 1); }
+        if n == 1 || n == 2 { return (n + 1, 1); }
+        let mut steps = 1;
+        n += 2;
+        while !Primes::is_prime(n) {
+            n += 2;
+            steps += 1;
+        }
+        (n, steps)
+    }
+}
+
+impl<MM> Calculator<MMFlt, MMInt> for PartialSum<MM>
+where
+    MM: Calculator<MMFlt, MMInt, Calculated = Phase<MMFlt, MMInt>>,
+{
+    type Calculated = Phase<MMFlt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        phase.setinput(n);
+        while phase.prev().0 <= n as f64 {
+            let k = phase.prev().0 as MMInt;
+            *phase.result_mut() += self.inner.calculate(k, &mut inner)?.result().0;
+            *phase.prev_mut() += 1.0;
+        }
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl<MM> Calculator<MMInt, MMInt> for PartialSum<MM>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        phase.setinput(n);
+        while *phase.prev() <= n {
+            let value = *self.inner.calculate(*phase.prev(), &mut inner)?.result();
+            *phase.result_mut() = phase.result().checked_add(value).ok_or(MachineError::Overflow)?;
+            *phase.prev_mut() += 1;
+        }
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for PrimeSum {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[1] = Primes::next_prime(phase[1]);
+            phase[0] = phase[0].checked_add(phase[1]).ok_or(MachineError::Overflow)?;
+        }
+        Ok(phase.to_owned())
+    }
 }
 
-/// Implements the Fibonacci sequence to calculate
-/// the Nth value. Results are cached, with lookup
-/// in reverse order, to find the closest value
-/// calculated to a new N, if N does not already
-/// exist.
-///
-/// ```
-/// use math_machines::{Machine, Fibonacci, lru_calculate};
-///
-/// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
-/// let result  = lru_calculate(machine, 26).expect("26th fibonacci");
-/// assert_eq!(result, 121393);
-/// ```
-#[derive(Debug)]
-pub struct Fibonacci;
-
-/// Implements the Harmonic series to calculate
-/// the Nth value. Results are cached, with lookup
-/// in reverse order, to find the closest value
-/// calculated to a new N, if N does not already
-/// exist.
-#[derive(Debug)]
-pub struct Harmonic;
+impl Calculator<MMInt, MMInt> for Primes {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        self.calculate_counted(n, phase).map(|(phase, _)| phase)
+    }
+    fn seed(&self, phase: &mut Self::Calculated) {
+        *phase.result_mut() = 2;
+    }
+}
 
-/// Implements the sequence of prime numbers to
-/// calculate the Nth value in the sequence.
-/// Results are cached, with lookup
-/// in reverse order, to find the closest value
-/// calculated to a new N, if N does not already
-/// exist.
-///
-/// ```
-/// use math_machines::{Machine, Primes, lru_calculate};
-///
-/// let machine = &mut Machine::new(Primes{}, 128, 50);
-/// let result  = lru_calculate(machine, 26).expect("26th prime");
-/// assert_eq!(result, 101);
-/// ```
-#[derive(Debug)]
-pub struct Primes;
+impl CountingCalculator<MMInt, MMInt> for Primes {
+    fn calculate_counted(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<(Self::Calculated, MMSize)> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        let mut steps = 0;
+        for _ in start..stahp {
+            let (prime, considered) = Primes::next_prime_counted(*phase.result());
+            *phase.result_mut() = prime;
+            steps += considered;
+        }
+        Ok((phase.to_owned(), steps))
+    }
+}
 
-impl Calculator<MMInt, MMInt> for Fibonacci {
+impl Calculator<MMInt, MMInt> for RankOfApparition {
     type Calculated = Phase<MMInt, MMInt>;
     fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
-        let (start, stahp) = (&mut phase.input().to_owned(), n);
+        let fib   = FibonacciMod{modulus: n, fast: false};
+        let inner = &mut Phase::new();
+        fib.seed(inner);
+        // The 1st zero after F(0) is within 2N
+        // steps of the sequence.
+        let mut rank = 1;
+        while *fib.calculate(rank, inner)?.result() != 0 {
+            rank += 1;
+        }
         phase.setinput(n);
-        for _ in *start..stahp {
-            phase.rotate(1);
-            phase[0] = cmp::max(1, phase[1] + phase[2]);
+        phase[0] = rank;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl<MM> Calculator<MMFlt, MMInt> for Ratio<MM>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    type Calculated = Phase<MMFlt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        if n == 0 {
+            return Err(MachineError::InvalidInput);
         }
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        let prev = *self.inner.calculate(n - 1, &mut inner)?.result();
+        if prev == 0 {
+            return Err(MachineError::DivisionByZero);
+        }
+        let curr = *self.inner.calculate(n, &mut inner)?.result();
+        phase.setinput(n);
+        phase[0] = OrderedFloat(curr as f64 / prev as f64);
         Ok(phase.to_owned())
     }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0 && self.inner.valid_input(n)
+    }
 }
 
-impl Calculator<MMFlt, MMInt> for Harmonic {
+impl<MM> Calculator<MMFlt, MMInt> for Reciprocal<MM>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
     type Calculated = Phase<MMFlt, MMInt>;
     fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
-        let (start, stahp) = (&mut phase.input().to_owned(), n);
+        let mut inner = Phase::new();
+        self.inner.seed(&mut inner);
+        let value = *self.inner.calculate(n, &mut inner)?.result();
+        if value == 0 {
+            return Err(MachineError::DivisionByZero);
+        }
         phase.setinput(n);
-        for _ in *start..stahp {
-            phase[1] = phase[1]+1.0;
-            phase[0] = phase[0] + 1.0/(phase[1].0);
+        phase[0] = OrderedFloat(1.0 / value as f64);
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Repunit {
+    /// Repunit of N ones is a prime number or
+    /// not. Repunits beyond the bounds of MMInt
+    /// are not considered prime.
+    ///
+    /// ```
+    /// use math_machines::Repunit;
+    ///
+    /// let primes: Vec<_> = (0..=40).filter(|n| Repunit::is_repunit_prime(*n)).collect();
+    /// assert_eq!(primes, vec![2, 19, 23]);
+    /// ```
+    pub fn is_repunit_prime(n: MMInt) -> bool {
+        // R(ab) is divisible by R(a), so only
+        // prime N can give a prime repunit.
+        if !Primes::is_prime_mr(n) {
+            return false;
+        }
+        (0..n)
+            .try_fold(0 as MMInt, |r, _| r.checked_mul(10)?.checked_add(1))
+            .is_some_and(Primes::is_prime_mr)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Repunit {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (start, stahp) = (phase.input().to_owned(), n);
+        phase.setinput(n);
+        for _ in start..stahp {
+            phase[0] = phase[0]
+                .checked_mul(10)
+                .and_then(|r| r.checked_add(1))
+                .ok_or(MachineError::Overflow)?;
         }
         Ok(phase.to_owned())
     }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // R(40) exceeds the bounds of MMInt.
+        *n <= 39
+    }
 }
 
-impl Primes {
-    /// Integer is a prime number or not.
+impl Reverse {
+    /// Reverse the digits of an integer in the
+    /// given base, if the result fits within
+    /// `MMInt`.
+    ///
+    /// Panics if `base` is less than 2.
     ///
     /// ```
-    /// use math_machines::Primes;
+    /// use math_machines::Reverse;
     ///
-    /// assert_eq!(Primes::is_prime(98), false);
-    /// assert_eq!(Primes::is_prime(144), false);
-    /// assert_eq!(Primes::is_prime(181), true);
+    /// assert_eq!(Reverse::reverse(120, 10), Some(21));
+    /// assert_eq!(Reverse::reverse(0x12, 16), Some(0x21));
+    /// assert_eq!(Reverse::reverse(u128::MAX, 10), None);
     /// ```
-    pub fn is_prime(n: MMInt) -> bool {
-        if n <= 1 { return false; }
-        if n <= 3 { return true; }
-        if n % 2 == 0 || n % 3 == 0 { return false; }
+    pub fn reverse(n: MMInt, base: MMInt) -> Option<MMInt> {
+        digits(n, base)
+            .iter()
+            .try_fold(0 as MMInt, |acc, d| acc.checked_mul(base)?.checked_add(*d))
+    }
+}
 
-        let mut stepper: MMInt = 5;
-        while stepper.pow(2) <= n {
-            if n % stepper == 0 || n % (stepper + 2) == 0 {
-                return false;
+impl Default for Reverse {
+    fn default() -> Self {
+        Self{base: 10}
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Reverse {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        if self.base < 2 {
+            return Err(MachineError::InvalidInput);
+        }
+        phase.setinput(n);
+        phase[0] = Reverse::reverse(n, self.base).ok_or(MachineError::Overflow)?;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, _n: &MMInt) -> bool {
+        self.base >= 2
+    }
+}
+
+impl Calculator<MMInt, MMInt> for ReverseAddSteps {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let (mut value, mut steps) = (n, 0);
+        let mut reversed = Reverse::reverse(value, 10).ok_or(MachineError::Overflow)?;
+        while reversed != value && steps < self.limit {
+            value    = value.checked_add(reversed).ok_or(MachineError::Overflow)?;
+            reversed = Reverse::reverse(value, 10).ok_or(MachineError::Overflow)?;
+            steps   += 1;
+        }
+        phase.setinput(n);
+        phase[0] = steps;
+        Ok(phase.to_owned())
+    }
+}
+
+impl<MM> RollingMean<MM> {
+    /// Create a new instance of `RollingMean`
+    /// with an empty window.
+    pub fn new(inner: MM, window: MMSize) -> Self {
+        Self{inner, window, values: RefCell::new(VecDeque::new()), last: RefCell::new(Phase::new())}
+    }
+}
+
+impl<MM> Calculator<MMFlt, MMInt> for RollingMean<MM>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    type Calculated = Phase<MMFlt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let values = &mut self.values.borrow_mut();
+        let inner  = &mut self.last.borrow_mut();
+        let width  = self.window.max(1);
+
+        // Move the window on where N follows the
+        // last N, otherwise fill it from scratch.
+        let start = if !values.is_empty() && *inner.input() + 1 == n {
+            n
+        } else {
+            values.clear();
+            **inner = Phase::new();
+            self.inner.seed(inner);
+            (n + 1).saturating_sub(width as MMInt)
+        };
+        for k in start..=n {
+            match self.inner.calculate(k, inner) {
+                Ok(calc) => values.push_back(*calc.result()),
+                Err(err) => {
+                    values.clear();
+                    return Err(err);
+                },
             }
-            stepper += 6;
         }
-        true
+        while values.len() > width {
+            values.pop_front();
+        }
+
+        let total: f64 = values.iter().map(|v| *v as f64).sum();
+        phase.setinput(n);
+        phase[0] = OrderedFloat(total / values.len() as f64);
+        Ok(phase.to_owned())
     }
-    /// Get the next sequential prime number.
+    fn valid_input(&self, n: &MMInt) -> bool {
+        self.inner.valid_input(n)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for RulerSequence {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = n.trailing_zeros() as MMInt;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Stirling2 {
+    /// Create a new instance of `Stirling2` with
+    /// an empty history.
+    pub fn new(k: MMInt) -> Self {
+        Self{k, history: RefCell::new(vec![])}
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Stirling2 {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let history = &mut self.history.borrow_mut();
+        let width   = self.k as MMSize + 1;
+        if history.is_empty() {
+            let mut row = vec![0; width];
+            row[0] = 1;
+            history.push(row);
+        }
+        // S(n, k) = k*S(n-1, k) + S(n-1, k-1)
+        while history.len() as MMInt <= n {
+            let prev = history.last().expect("stirling history");
+            let mut row = vec![0; width];
+            for j in 1..width {
+                row[j] = (j as MMInt).checked_mul(prev[j])
+                    .and_then(|v| v.checked_add(prev[j - 1]))
+                    .ok_or(MachineError::Overflow)?;
+            }
+            history.push(row);
+        }
+        phase.setinput(n);
+        phase[0] = history[n as MMSize][width - 1];
+        Ok(phase.to_owned())
+    }
+}
+
+impl SumOfSquaresReps {
+    /// Number of ways to write N as an ordered
+    /// sum of `k` squares, by convolving the ways
+    /// for `k - 1` squares with a single square.
+    pub fn convolved(k: MMSize, n: MMInt) -> MachineResult<MMInt> {
+        let n = n as usize;
+        let mut reps = vec![0 as MMInt; n + 1];
+        reps[0] = 1;
+        for _ in 0..k {
+            let mut next = vec![0 as MMInt; n + 1];
+            for (m, count) in next.iter_mut().enumerate() {
+                // Each nonzero root adds both signs.
+                for root in 0..=m.isqrt() {
+                    let ways = if root == 0 { 1 } else { 2 };
+                    *count = reps[m - root * root]
+                        .checked_mul(ways)
+                        .and_then(|v| v.checked_add(*count))
+                        .ok_or(MachineError::Overflow)?;
+                }
+            }
+            reps = next;
+        }
+        Ok(reps[n])
+    }
+    /// Number of ways to write N as an ordered
+    /// sum of 2 squares, as `4(d1(n) - d3(n))`
+    /// where `d1` and `d3` count the divisors of N
+    /// that are 1 and 3 mod 4.
+    fn two_squares(n: MMInt) -> MMInt {
+        if n == 0 { return 1; }
+
+        let (mut d1, mut d3) = (0, 0);
+        let mut count = |d: MMInt| match d % 4 {
+            1 => d1 += 1,
+            3 => d3 += 1,
+            _ => (),
+        };
+        for d in 1..=n.isqrt() {
+            if n.is_multiple_of(d) {
+                count(d);
+                if d * d != n {
+                    count(n / d);
+                }
+            }
+        }
+        4 * (d1 - d3)
+    }
+}
+
+impl Calculator<MMInt, MMInt> for SumOfSquaresReps {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = match self.k {
+            2 => SumOfSquaresReps::two_squares(n),
+            k => SumOfSquaresReps::convolved(k, n)?,
+        };
+        Ok(phase.to_owned())
+    }
+}
+
+impl SumOfTwoSquares {
+    /// Integer is the sum of two squares or not,
+    /// where no prime factor `3 mod 4` may appear
+    /// to an odd power.
     ///
     /// ```
-    /// use math_machines::Primes;
+    /// use math_machines::SumOfTwoSquares;
     ///
-    /// assert_eq!(Primes::next_prime(3517), 3527);
-    /// assert_eq!(Primes::next_prime(7489), 7499);
+    /// assert_eq!(SumOfTwoSquares::is_sum_of_two_squares(45), true);
+    /// assert_eq!(SumOfTwoSquares::is_sum_of_two_squares(12), false);
     /// ```
-    pub fn next_prime(mut n: MMInt) -> MMInt {
-        if n == 0 { return 2; }
-        if n == 1 || n == 2 { return n + 1; }
-        n += 2;
-        while !Primes::is_prime(n) {
-            n += 2;
+    pub fn is_sum_of_two_squares(n: MMInt) -> bool {
+        Primes::factorize(n)
+            .iter()
+            .all(|(p, e)| *p % 4 != 3 || e.is_multiple_of(2))
+    }
+}
+
+impl Calculator<MMInt, MMInt> for SumOfTwoSquares {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = SumOfTwoSquares::is_sum_of_two_squares(n) as MMInt;
+        Ok(phase.to_owned())
+    }
+}
+
+impl Tetrahedral {
+    /// Integer is a tetrahedral number or not,
+    /// checked against the tetrahedral numbers
+    /// nearest to `cbrt(6x)`.
+    ///
+    /// ```
+    /// use math_machines::Tetrahedral;
+    ///
+    /// assert_eq!(Tetrahedral::is_tetrahedral(35), true);
+    /// assert_eq!(Tetrahedral::is_tetrahedral(36), false);
+    /// assert_eq!(Tetrahedral::is_tetrahedral(0), true);
+    /// let tetrahedrals: Vec<_> = (0..100).filter(|x| Tetrahedral::is_tetrahedral(*x)).collect();
+    /// assert_eq!(tetrahedrals, vec![0, 1, 4, 10, 20, 35, 56, 84]);
+    /// ```
+    pub fn is_tetrahedral(x: MMInt) -> bool {
+        let root = (6.0 * x as f64).cbrt() as MMInt;
+        (root.saturating_sub(2)..=root + 2)
+            .any(|n| Tetrahedral{}.calculate_direct(n).is_ok_and(|t| t == x))
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Tetrahedral {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = self.calculate_direct(n)?;
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // Values past N of 12686161381662 exceed the
+        // bounds of MMInt.
+        *n <= 12_686_161_381_662
+    }
+    fn as_direct(&self) -> Option<&dyn DirectCalculator<MMInt, MMInt>> {
+        Some(self)
+    }
+}
+
+impl DirectCalculator<MMInt, MMInt> for Tetrahedral {
+    fn calculate_direct(&self, n: MMInt) -> MachineResult<MMInt> {
+        let Some(c) = n.checked_add(2) else {
+            return Err(MachineError::Overflow);
+        };
+        // One of n, n+1 is even and one of n, n+1,
+        // n+2 is a multiple of 3, so divide early.
+        let (mut a, mut b, mut c) = (n, n + 1, c);
+        if a.is_multiple_of(2) { a /= 2; } else { b /= 2; }
+        match n % 3 {
+            0 => a /= 3,
+            1 => c /= 3,
+            _ => b /= 3,
         }
-        n
+        a.checked_mul(b)
+            .and_then(|v| v.checked_mul(c))
+            .ok_or(MachineError::Overflow)
     }
 }
 
-impl Calculator<MMInt, MMInt> for Primes {
+impl Totient {
+    /// Count of integers up to N coprime to N.
+    ///
+    /// ```
+    /// use math_machines::Totient;
+    ///
+    /// assert_eq!(Totient::totient(36), 12);
+    /// assert_eq!(Totient::totient(97), 96);
+    /// assert_eq!(Totient::totient(1), 1);
+    /// ```
+    pub fn totient(n: MMInt) -> MMInt {
+        Primes::factorize(n)
+            .iter()
+            .fold(n, |acc, (p, _)| acc / p * (p - 1))
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Totient {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        phase.setinput(n);
+        phase[0] = Totient::totient(n);
+        Ok(phase.to_owned())
+    }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        *n > 0
+    }
+}
+
+impl Calculator<MMInt, MMInt> for TotientSum {
     type Calculated = Phase<MMInt, MMInt>;
     fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
         let (start, stahp) = (phase.input().to_owned(), n);
         phase.setinput(n);
-        for _ in start..stahp {
-            phase[0] = Primes::next_prime(phase[0]);
+        for k in (start + 1)..=stahp {
+            phase[0] = phase[0].checked_add(Totient::totient(k)).ok_or(MachineError::Overflow)?;
+        }
+        Ok(phase.to_owned())
+    }
+}
+
+impl Ulam {
+    /// Count the ways the integer is the sum of
+    /// two distinct values of an ascending
+    /// history.
+    fn count_sums(history: &[MMInt], n: MMInt) -> MMSize {
+        let (mut lo, mut hi, mut count) = (0, history.len(), 0);
+        while lo + 1 < hi {
+            match (history[lo] + history[hi - 1]).cmp(&n) {
+                Ordering::Less => lo += 1,
+                Ordering::Greater => hi -= 1,
+                Ordering::Equal => {
+                    count += 1;
+                    (lo, hi) = (lo + 1, hi - 1);
+                }
+            }
+        }
+        count
+    }
+}
+
+impl Calculator<MMInt, MMInt> for Ulam {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let history = &mut self.history.borrow_mut();
+        if history.is_empty() {
+            history.extend([1, 2]);
+        }
+        phase.setinput(n);
+        while history.len() as MMInt <= n {
+            let mut next = history.last().expect("ulam history") + 1;
+            while Ulam::count_sums(history, next) != 1 {
+                next += 1;
+            }
+            history.push(next);
+        }
+        phase[0] = history[n as MMSize];
+        Ok(phase.to_owned())
+    }
+}
+
+impl Calculator<MMInt, MMInt> for WedderburnEtherington {
+    type Calculated = Phase<MMInt, MMInt>;
+    fn calculate(&self, n: MMInt, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+        let history = &mut self.history.borrow_mut();
+        if history.is_empty() {
+            history.extend([0, 1]);
+        }
+        // Split the leaves between two subtrees,
+        // where equal halves are unordered pairs
+        // of the trees of either half.
+        while history.len() as MMInt <= n {
+            let leaves = history.len();
+            let mut next: MMInt = 0;
+            for i in 1..leaves.div_ceil(2) {
+                next = history[i].checked_mul(history[leaves - i])
+                    .and_then(|v| v.checked_add(next))
+                    .ok_or(MachineError::Overflow)?;
+            }
+            if leaves.is_multiple_of(2) {
+                let half = history[leaves / 2];
+                next = half.checked_mul(half + 1)
+                    .map(|v| v / 2)
+                    .and_then(|v| v.checked_add(next))
+                    .ok_or(MachineError::Overflow)?;
+            }
+            history.push(next);
         }
+        phase.setinput(n);
+        phase[0] = history[n as MMSize];
         Ok(phase.to_owned())
     }
+    fn valid_input(&self, n: &MMInt) -> bool {
+        // W(107) exceeds the bounds of MMInt.
+        *n <= 106
+    }
 }
@@ -1,12 +1,16 @@
+pub mod builders;
 pub mod caches;
 pub mod calculators;
 pub mod machines;
 pub mod phases;
+#[cfg(feature = "test-util")]
+pub mod testing;
 
+pub use builders::SeqBuilder;
 pub use caches::{Caches, MachineCache};
 pub use calculators::*;
-pub use machines::{Machine, lru_calculate, raw_calculate};
-pub use phases::{MMFlt, MMInt};
+pub use machines::{Machine, MachineState, into_fn, lru_calculate, raw_calculate};
+pub use phases::{ApproxF64, Counted, MMFlt, MMInt, MMSInt};
 
 /// ```
 /// use math_machines as mm;
@@ -1,21 +1,92 @@
 use crate::caches::{Caches, MachineCache, CacheResult};
 use crate::calculators::Calculator;
-use crate::phases::{MMSize, Newable, Phase};
+use crate::phases::{MMFlt, MMInt, MMSize, Newable, Phase};
 
-use std::fmt::Debug;
+use ordered_float::OrderedFloat;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
 use std::hash::Hash;
+use std::ops::Add;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
 
 /// Error occurred during some calculation.
 #[derive(Debug)]
-pub enum MachineError {}
+pub enum MachineError {
+    /// Sequence did not converge within the
+    /// allowed number of iterations.
+    DidNotConverge,
+    /// Calculation divided by zero.
+    DivisionByZero,
+    /// Input is outside the domain of the
+    /// calculator.
+    InvalidInput,
+    /// Calculation exceeded the bounds of the
+    /// numeric type used.
+    Overflow,
+}
 /// Alias for Result<T, MachineError>.
 pub type MachineResult<T> = Result<T, MachineError>;
+/// Cache entries, usage counts and limits of a
+/// `Machine`, less the calculator itself.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MachineState<T, I> {
+    pub entries: Vec<Phase<T, I>>,
+    pub usages: Vec<(I, MMSize)>,
+    pub max_entry_cap: MMSize,
+    pub max_usage_age: MMSize,
+}
+/// Value calculated by a `Machine`, either
+/// exactly or, where the exact value overflows,
+/// as its residue by the fallback modulus.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome<T> {
+    /// Exact value of the calculation.
+    Exact(T),
+    /// Residue of the exact value by a modulus.
+    Residue { value: MMInt, modulus: MMInt },
+}
+/// Callback fired for each phase inserted into,
+/// or evicted from, the cache of a `Machine`.
+/// Callbacks are `Send`, so a `Machine` can be
+/// moved between threads.
+type PhaseHook<T, I> = Box<dyn FnMut(&Phase<T, I>) + Send>;
 
 
 /// Handles all the operations from `calculate`,
 /// `update` and `lookup` on the cache, and
 /// cleanup on the cache as LRU is needed.
-#[derive(Debug)]
+///
+/// Inputs need only be `Clone`, allowing for
+/// inputs which are not `Copy`.
+///
+/// ```
+/// use math_machines::{Calculator, MMInt, Machine, lru_calculate};
+/// use math_machines::machines::MachineResult;
+/// use math_machines::phases::Phase;
+///
+/// #[derive(Debug)]
+/// struct WordLength;
+///
+/// impl Calculator<MMInt, String> for WordLength {
+///     type Calculated = Phase<MMInt, String>;
+///     fn calculate(&self, n: String, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+///         phase[0] = n.len() as MMInt;
+///         phase.setinput(n);
+///         Ok(phase.to_owned())
+///     }
+/// }
+///
+/// let machine = &mut Machine::new(WordLength{}, 128, 50);
+/// let result  = lru_calculate(machine, String::from("machine")).expect("word length");
+/// assert_eq!(result, 7);
+///
+/// fn is_send<T: Send>(_: &T) {}
+/// machine.on_evict(|_| ());
+/// is_send(machine);
+/// ```
 pub struct Machine<T, I, MM>
 where
     T: Clone + Default + Ord,
@@ -26,14 +97,102 @@ where
     machine: MM,
     max_entry_cap: MMSize,
     max_usage_age: MMSize,
+    on_evict: Option<PhaseHook<T, I>>,
+    on_insert: Option<PhaseHook<T, I>>,
+    fallback_modulus: Option<MMInt>,
+}
+
+impl<T, I, MM> Debug for Machine<T, I, MM>
+where
+    T: Clone + Debug + Default + Ord,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
+    MM: Calculator<T, I> + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Machine")
+            .field("cache", &self.cache)
+            .field("machine", &self.machine)
+            .field("max_entry_cap", &self.max_entry_cap)
+            .field("max_usage_age", &self.max_usage_age)
+            .field("on_evict", &self.on_evict.is_some())
+            .field("on_insert", &self.on_insert.is_some())
+            .field("fallback_modulus", &self.fallback_modulus)
+            .finish()
+    }
 }
 
 impl<T, I, MM> Machine<T, I, MM>
 where
     T: Clone + Debug + Default + Ord,
-    I: Clone + Copy + Debug + Default + Eq + Hash + Ord + PartialEq,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
     MM: Calculator<T, I>,
 {
+    /// Calculate the given inputs, in ascending
+    /// order and without duplicates, mapping each
+    /// input to its value.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// let values  = machine.calculate_many(&[20, 5, 5, 10]).expect("fibonacci values");
+    /// assert_eq!(values.len(), 3);
+    /// assert_eq!(values[&5], 5);
+    /// assert_eq!(values[&10], 55);
+    /// assert_eq!(values[&20], 6765);
+    /// ```
+    pub fn calculate_many(&mut self, ns: &[I]) -> MachineResult<HashMap<I, T>>
+    where
+        MM: Calculator<T, I, Calculated = Phase<T, I>>,
+    {
+        let mut ns = ns.to_vec();
+        ns.sort();
+        ns.dedup();
+        ns.into_iter()
+            .map(|n| lru_calculate(self, n.clone()).map(|r| (n, r)))
+            .collect()
+    }
+    /// Calculate the Nth value exactly, or, where
+    /// the exact value is out of bounds and a
+    /// fallback modulus is set, its residue by the
+    /// modulus, if the calculator has a modular
+    /// form. N rejected by the `valid_input` of a
+    /// modular calculator is taken as out of
+    /// bounds.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    /// use math_machines::machines::Outcome;
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50).with_fallback_modulus(1_000_000_007);
+    /// assert_eq!(machine.calculate_or_residue(10).expect("10th fibonacci"), Outcome::Exact(55));
+    /// assert_eq!(
+    ///     machine.calculate_or_residue(300).expect("300th fibonacci"),
+    ///     Outcome::Residue{value: 644264086, modulus: 1_000_000_007});
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// assert!(machine.calculate_or_residue(300).is_err());
+    /// ```
+    pub fn calculate_or_residue(&mut self, n: I) -> MachineResult<Outcome<T>>
+    where
+        MM: Calculator<T, I, Calculated = Phase<T, I>>,
+    {
+        match lru_calculate(self, n.clone()) {
+            Ok(value) => Ok(Outcome::Exact(value)),
+            // Modular calculators only reject N whose
+            // values overflow, as `ModularCalculator`
+            // requires.
+            Err(MachineError::Overflow | MachineError::InvalidInput)
+                if self.fallback_modulus.is_some() && self.machine.as_modular().is_some() =>
+            {
+                let modulus = self.fallback_modulus.expect("fallback modulus");
+                let modular = self.machine.as_modular().expect("modular calculator");
+                let value   = modular.calculate_mod(n, modulus)?;
+                Ok(Outcome::Residue{value, modulus})
+            },
+            Err(err) => Err(err),
+        }
+    }
     /// Do the internal calculation.
     fn calculate(&self, n: I, phase: &mut MM::Calculated) -> MachineResult<MM::Calculated> {
         self.machine.calculate(n, phase)
@@ -42,47 +201,557 @@ where
     pub fn new(machine: MM, max_entries: MMSize, max_age: MMSize) -> Self {
         Machine{
             cache: MachineCache::new(),
-            machine: machine,
+            machine,
             max_entry_cap: max_entries,
-            max_usage_age: max_age
+            max_usage_age: max_age,
+            on_evict: None,
+            on_insert: None,
+            fallback_modulus: None,
         }
     }
     fn drop_invalid(&mut self) -> CacheResult<Vec<Phase<T, I>>> {
         self.cache.drop_invalid(|_| true)
     }
-    fn is_too_big(&self) -> bool {
+    /// Replace the cache and limits of this
+    /// machine with those from a `save_state`.
+    /// Usage counts are matched to the entries
+    /// of the state, as in
+    /// `MachineCache::restore_full`.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Primes, lru_calculate};
+    ///
+    /// let machine = &mut Machine::new(Primes{}, 4, 2);
+    /// for n in [5, 10, 20] {
+    ///     lru_calculate(machine, n).expect("Nth prime");
+    /// }
+    /// let mut state = machine.save_state();
+    /// state.usages.clear();
+    ///
+    /// machine.load_state(state);
+    /// for n in 21..30 {
+    ///     lru_calculate(machine, n).expect("Nth prime");
+    /// }
+    /// ```
+    pub fn load_state(&mut self, state: MachineState<T, I>) {
+        self.cache.restore_full(state.entries, state.usages);
+        self.max_entry_cap = state.max_entry_cap;
+        self.max_usage_age = state.max_usage_age;
+    }
+    /// Set the age at which cached entries are
+    /// evicted.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, lru_calculate};
+    /// use std::time::Duration;
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// machine.set_ttl(Duration::from_secs(3600));
+    /// for n in 0..5 {
+    ///     lru_calculate(machine, n).expect("Nth fibonacci");
+    /// }
+    /// assert!(!machine.would_evict());
+    ///
+    /// machine.set_ttl(Duration::ZERO);
+    /// assert!(machine.would_evict());
+    /// lru_calculate(machine, 10).expect("10th fibonacci");
+    /// assert_eq!(machine.save_state().entries.len(), 1);
+    /// ```
+    pub fn set_ttl(&mut self, d: Duration) {
+        self.cache.set_ttl(d);
+    }
+    /// Copy out the cache and limits of this
+    /// machine. The calculator is not included,
+    /// and is expected to be reconstructed by the
+    /// caller.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, lru_calculate};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// for n in [5, 10, 20] {
+    ///     lru_calculate(machine, n).expect("Nth fibonacci");
+    /// }
+    /// let state = machine.save_state();
+    /// #[cfg(feature = "serde")]
+    /// let state = serde_json::from_str(&serde_json::to_string(&state).expect("json"))
+    ///     .expect("machine state");
+    ///
+    /// let loaded = &mut Machine::new(Fibonacci{}, 1, 1);
+    /// loaded.load_state(state);
+    /// assert_eq!(loaded.save_state().entries, machine.save_state().entries);
+    /// for n in 0..30 {
+    ///     assert_eq!(lru_calculate(loaded, n).ok(), lru_calculate(machine, n).ok());
+    /// }
+    /// ```
+    pub fn save_state(&self) -> MachineState<T, I> {
+        let (entries, usages) = self.cache.snapshot_full();
+        MachineState{
+            entries,
+            usages,
+            max_entry_cap: self.max_entry_cap,
+            max_usage_age: self.max_usage_age,
+        }
+    }
+    /// Cache has reached the maximum number of
+    /// entries.
+    pub fn is_too_big(&self) -> bool {
         self.cache.len() >= self.max_entry_cap()
     }
-    fn is_too_old(&self) -> bool {
+    /// Cache has an entry which has reached the
+    /// maximum usage age.
+    pub fn is_too_old(&self) -> bool {
         self.cache.highest_usage() >= self.max_usage_age()
     }
     fn lookup(&mut self, n: I) -> CacheResult<Phase<T, I>> {
         self.cache.find_closest(n)
     }
+    /// Cache would evict entries before the next
+    /// calculation, including any expired
+    /// entries.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, lru_calculate};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 4, 50);
+    /// for n in 0..3 {
+    ///     lru_calculate(machine, n).expect("Nth fibonacci");
+    /// }
+    /// assert!(!machine.would_evict());
+    ///
+    /// lru_calculate(machine, 3).expect("3rd fibonacci");
+    /// assert!(machine.is_too_big());
+    /// assert!(!machine.is_too_old());
+    /// assert!(machine.would_evict());
+    /// ```
+    pub fn would_evict(&self) -> bool {
+        if self.cache.has_expired() {
+            return true;
+        }
+        self.is_too_big() || self.is_too_old()
+    }
     fn max_entry_cap(&self) -> MMSize {
         self.max_entry_cap
     }
     fn max_usage_age(&self) -> MMSize {
         self.max_usage_age
     }
+    /// Register a callback fired for each phase
+    /// dropped from the cache on eviction.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, lru_calculate};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let evicted = Arc::new(Mutex::new(vec![]));
+    /// let machine = &mut Machine::new(Fibonacci{}, 4, 50);
+    ///
+    /// let seen = evicted.clone();
+    /// machine.on_evict(move |p| seen.lock().expect("evicted").push(*p.input()));
+    /// for n in 0..8 {
+    ///     lru_calculate(machine, n).expect("Nth fibonacci");
+    /// }
+    /// assert_eq!(*evicted.lock().expect("evicted"), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn on_evict(&mut self, cb: impl FnMut(&Phase<T, I>) + Send + 'static) {
+        self.on_evict = Some(Box::new(cb));
+    }
+    /// Register a callback fired for each phase
+    /// newly inserted into the cache.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, lru_calculate};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let inserted = Arc::new(Mutex::new(vec![]));
+    /// let machine  = &mut Machine::new(Fibonacci{}, 128, 50);
+    ///
+    /// let seen = inserted.clone();
+    /// machine.on_insert(move |p| seen.lock().expect("inserted").push((*p.input(), *p.result())));
+    /// for n in 0..5 {
+    ///     lru_calculate(machine, n).expect("Nth fibonacci");
+    /// }
+    /// lru_calculate(machine, 3).expect("3rd fibonacci");
+    /// assert_eq!(*inserted.lock().expect("inserted"), vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 3)]);
+    /// ```
+    pub fn on_insert(&mut self, cb: impl FnMut(&Phase<T, I>) + Send + 'static) {
+        self.on_insert = Some(Box::new(cb));
+    }
+    /// Calculate the Nth value, returning the
+    /// whole phase of the calculation rather than
+    /// only its result.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// let phase   = machine.phase_at(10).expect("10th fibonacci");
+    /// assert_eq!(*phase.result(), 55);
+    /// assert_eq!(*phase.input(), 10);
+    /// assert_eq!(phase[1..], [34, 21]);
+    /// ```
+    pub fn phase_at(&mut self, n: I) -> MachineResult<Phase<T, I>>
+    where
+        MM: Calculator<T, I, Calculated = Phase<T, I>>,
+    {
+        lru_calculate_phase(self, n)
+    }
+    /// Calculate and cache precisely the given
+    /// inputs, stopping at the first failed
+    /// calculation.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// machine.preload([5, 10, 20]).expect("preloaded fibonacci");
+    ///
+    /// let computed: Vec<_> = machine.iter_new(21).map(|(n, _)| n).collect();
+    /// assert_eq!(computed.len(), 18);
+    /// assert!(!computed.iter().any(|n| [5, 10, 20].contains(n)));
+    /// ```
+    pub fn preload(&mut self, ns: impl IntoIterator<Item = I>) -> MachineResult<()>
+    where
+        MM: Calculator<T, I, Calculated = Phase<T, I>>,
+    {
+        for n in ns {
+            lru_calculate_phase(self, n)?;
+        }
+        Ok(())
+    }
+    /// Set the modulus used by
+    /// `calculate_or_residue` where the exact
+    /// value is out of bounds.
+    pub fn with_fallback_modulus(mut self, modulus: MMInt) -> Self {
+        self.fallback_modulus = Some(modulus);
+        self
+    }
     fn update(&mut self, phase: Phase<T, I>) {
-        let ret = self.cache.push(phase.clone());
-        ret
+        if let Some(cb) = self.on_insert.as_mut() {
+            if !self.cache.contains(phase.input()) {
+                cb(&phase);
+            }
+        }
+        self.cache.push(phase)
+    }
+}
+
+impl<T, MM> Machine<T, MMInt, MM>
+where
+    T: Clone + Debug + Default + Ord,
+    MM: Calculator<T, MMInt, Calculated = Phase<T, MMInt>>,
+{
+    /// Calculate the values for N from 0, writing
+    /// each into the given buffer until it is
+    /// full.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// let mut buf = [0u128; 10];
+    /// machine.fill(&mut buf).expect("fibonacci values");
+    /// assert_eq!(buf, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    /// ```
+    pub fn fill(&mut self, buf: &mut [T]) -> MachineResult<()> {
+        for (n, slot) in buf.iter_mut().enumerate() {
+            *slot = lru_calculate(self, n as MMInt)?;
+        }
+        Ok(())
+    }
+    /// Find the first N, up to and including
+    /// `max_n`, whose value exceeds `threshold`.
+    /// Returns `None` if no value exceeds it.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// assert_eq!(machine.first_exceeding(1000, 100).expect("fibonacci"), Some(17));
+    /// assert_eq!(machine.first_exceeding(1000, 10).expect("fibonacci"), None);
+    /// ```
+    pub fn first_exceeding(&mut self, threshold: T, max_n: MMInt) -> MachineResult<Option<MMInt>> {
+        for n in 0..=max_n {
+            if lru_calculate(self, n)? > threshold {
+                return Ok(Some(n));
+            }
+        }
+        Ok(None)
+    }
+    /// Find the N, up to and including `max_n`,
+    /// whose value is exactly `value`, by binary
+    /// search. Expects values to increase with N.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Primes};
+    ///
+    /// let machine = &mut Machine::new(Primes{}, 128, 50);
+    /// assert_eq!(machine.index_of(101, 100).expect("prime index"), Some(25));
+    /// assert_eq!(machine.index_of(2, 100).expect("prime index"), Some(0));
+    /// assert_eq!(machine.index_of(100, 100).expect("prime index"), None);
+    /// ```
+    pub fn index_of(&mut self, value: T, max_n: MMInt) -> MachineResult<Option<MMInt>> {
+        let (mut lo, mut hi) = (0, max_n);
+        while lo <= hi {
+            let mid = lo + (hi - lo) / 2;
+            match lru_calculate(self, mid)?.cmp(&value) {
+                Ordering::Equal => return Ok(Some(mid)),
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater if mid == 0 => break,
+                Ordering::Greater => hi = mid - 1,
+            }
+        }
+        Ok(None)
+    }
+    /// Iterate through values from `0..upto`,
+    /// yielding only the values that were not
+    /// already cached and required calculation.
+    /// Iteration stops at the first failed
+    /// calculation.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci, lru_calculate};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// for n in 0..10 {
+    ///     lru_calculate(machine, n).expect("Nth fibonacci");
+    /// }
+    ///
+    /// let computed: Vec<_> = machine.iter_new(15).collect();
+    /// assert_eq!(computed, vec![(10, 55), (11, 89), (12, 144), (13, 233), (14, 377)]);
+    /// ```
+    pub fn iter_new(&mut self, upto: MMInt) -> impl Iterator<Item = (MMInt, T)> + '_ {
+        (0..upto)
+            .map_while(move |n| {
+                if self.cache.contains(&n) {
+                    return Some(None);
+                }
+                lru_calculate(self, n).ok().map(|r| Some((n, r)))
+            })
+            .flatten()
+    }
+    /// Calculate values at each power of two N,
+    /// from 1 up to and including `upto`, pairing
+    /// each N with its value.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// let samples = machine.sample_log2(20).expect("fibonacci samples");
+    /// assert_eq!(samples, vec![(1, 1), (2, 1), (4, 3), (8, 21), (16, 987)]);
+    /// assert!(machine.sample_log2(0).expect("no samples").is_empty());
+    /// ```
+    pub fn sample_log2(&mut self, upto: MMInt) -> MachineResult<Vec<(MMInt, T)>> {
+        (0..MMInt::BITS)
+            .map(|k| 1 << k)
+            .take_while(|n| *n <= upto)
+            .map(|n| lru_calculate(self, n).map(|r| (n, r)))
+            .collect()
+    }
+    /// Calculate values from `0..=upto`, sending
+    /// each result through the channel as it is
+    /// calculated. Stops after the first failed
+    /// calculation, or once the receiver hangs up.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Fibonacci};
+    /// use math_machines::machines::MachineResult;
+    /// use std::sync::mpsc;
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// let receiver = thread::spawn(move || {
+    ///     rx.iter().map(|r: MachineResult<u128>| r.expect("Nth fibonacci")).collect::<Vec<_>>()
+    /// });
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// machine.stream(10, tx);
+    /// let values = receiver.join().expect("received values");
+    /// assert_eq!(values, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55]);
+    /// ```
+    pub fn stream(&mut self, upto: MMInt, tx: Sender<MachineResult<T>>) {
+        for n in 0..=upto {
+            let result = lru_calculate(self, n);
+            let failed = result.is_err();
+            if tx.send(result).is_err() || failed {
+                break;
+            }
+        }
+    }
+    /// Detect the period of the sequence, using
+    /// Floyd's tortoise and hare over the phase
+    /// state of each N. Returns `None` if no cycle
+    /// is found before reaching `max_n`.
+    ///
+    /// ```
+    /// use math_machines::{Machine, FibonacciMod};
+    ///
+    /// let machine = &mut Machine::new(FibonacciMod{modulus: 10, fast: false}, 128, 50);
+    /// let period  = machine.find_period(500).expect("pisano period");
+    /// assert_eq!(period, Some(60));
+    /// ```
+    pub fn find_period(&mut self, max_n: MMInt) -> MachineResult<Option<MMInt>> {
+        let state = |mm: &mut Self, n| {
+            lru_calculate_phase(mm, n).map(|p| p.phase().to_owned())
+        };
+
+        // Find a meeting point inside of the cycle.
+        let (mut tortoise, mut hare) = (1, 2);
+        while state(self, tortoise)? != state(self, hare)? {
+            (tortoise, hare) = (tortoise + 1, hare + 2);
+            if hare > max_n { return Ok(None); }
+        }
+        // Find the start of the cycle.
+        tortoise = 0;
+        while state(self, tortoise)? != state(self, hare)? {
+            (tortoise, hare) = (tortoise + 1, hare + 1);
+            if hare > max_n { return Ok(None); }
+        }
+        // Find the length of the cycle.
+        hare = tortoise + 1;
+        while state(self, tortoise)? != state(self, hare)? {
+            hare += 1;
+            if hare > max_n { return Ok(None); }
+        }
+        Ok(Some(hare - tortoise))
     }
 }
 
+impl<MM> Machine<MMInt, MMInt, MM>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    /// Ratio of the Nth value of the sequence to
+    /// the value before it, as a float. Errors for
+    /// N of 0, or where the value before is 0.
+    ///
+    /// ```
+    /// use math_machines::{Machine, Arithmetic, Fibonacci};
+    ///
+    /// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+    /// let rate    = machine.growth_rate(60).expect("fibonacci growth rate");
+    /// assert!((rate.0 - 1.618033988749895).abs() < 1e-12);
+    /// assert!(machine.growth_rate(1).is_err());
+    ///
+    /// let machine = &mut Machine::new(Arithmetic{first: 1, step: 3}, 128, 50);
+    /// let rate    = machine.growth_rate(100_000).expect("arithmetic growth rate");
+    /// assert!((rate.0 - 1.0).abs() < 1e-4);
+    /// ```
+    pub fn growth_rate(&mut self, n: MMInt) -> MachineResult<MMFlt> {
+        if n == 0 {
+            return Err(MachineError::InvalidInput);
+        }
+        let prev = lru_calculate(self, n - 1)?;
+        if prev == 0 {
+            return Err(MachineError::DivisionByZero);
+        }
+        let curr = lru_calculate(self, n)?;
+        Ok(OrderedFloat(curr as f64 / prev as f64))
+    }
+}
+
+impl<I, MM> Machine<MMFlt, I, MM>
+where
+    I: Add<Output = I> + Clone + Debug + Default + Eq + From<u8> + Hash + Ord + PartialEq,
+    MM: Calculator<MMFlt, I, Calculated = Phase<MMFlt, I>>,
+{
+    /// Estimate the limit of a float sequence.
+    /// Values are calculated until successive
+    /// results differ by less than `eps`,
+    /// returning the N where convergence occurred
+    /// and the value at N.
+    ///
+    /// ```
+    /// use math_machines::{Calculator, Machine, GoldenRatio};
+    /// use math_machines::machines::MachineResult;
+    /// use math_machines::phases::Phase;
+    /// use ordered_float::OrderedFloat;
+    ///
+    /// let machine = &mut Machine::new(GoldenRatio{}, 128, 50);
+    /// let (n, r)  = machine.converge(OrderedFloat(1e-9), 100).expect("convergence");
+    /// assert!(n < 100);
+    /// assert!((r.0 - 1.618033988749895).abs() < 1e-9);
+    ///
+    /// let machine = &mut Machine::new(GoldenRatio{}, 128, 50);
+    /// assert!(machine.converge(OrderedFloat(1e-9), 10).is_err());
+    ///
+    /// #[derive(Debug)]
+    /// struct Halving;
+    ///
+    /// impl Calculator<OrderedFloat<f64>, u32> for Halving {
+    ///     type Calculated = Phase<OrderedFloat<f64>, u32>;
+    ///     fn calculate(&self, n: u32, phase: &mut Self::Calculated) -> MachineResult<Self::Calculated> {
+    ///         phase.setinput(n);
+    ///         phase[0] = OrderedFloat(0.5f64.powi(n as i32));
+    ///         Ok(phase.to_owned())
+    ///     }
+    /// }
+    ///
+    /// let machine = &mut Machine::new(Halving{}, 128, 50);
+    /// let (n, r)  = machine.converge(OrderedFloat(1e-3), 100).expect("convergence");
+    /// assert_eq!((n, r.0), (10u32, 0.5f64.powi(10)));
+    /// ```
+    pub fn converge(&mut self, eps: MMFlt, max_n: I) -> MachineResult<(I, MMFlt)> {
+        let mut n    = I::from(0);
+        let mut prev = lru_calculate(self, n.clone())?;
+        while n < max_n {
+            n = n + I::from(1);
+            let curr = lru_calculate(self, n.clone())?;
+            if (curr - prev).abs() < eps.0 {
+                return Ok((n, curr));
+            }
+            prev = curr;
+        }
+        Err(MachineError::DidNotConverge)
+    }
+}
+
+/// Wrap a machine as a plain closure of N,
+/// calculating values with `lru_calculate`, so
+/// the cache is kept across calls.
+///
+/// ```
+/// use math_machines::{Machine, Fibonacci};
+/// use math_machines::machines::into_fn;
+/// use std::sync::{Arc, Mutex};
+///
+/// let inserted = Arc::new(Mutex::new(0));
+/// let mut machine = Machine::new(Fibonacci{}, 128, 50);
+/// let seen = inserted.clone();
+/// machine.on_insert(move |_| *seen.lock().expect("inserted") += 1);
+///
+/// let mut fib = into_fn(machine);
+/// assert_eq!(fib(10).expect("10th fibonacci"), 55);
+/// assert_eq!(*inserted.lock().expect("inserted"), 1);
+/// assert_eq!(fib(10).expect("10th fibonacci"), 55);
+/// assert_eq!(fib(12).expect("12th fibonacci"), 144);
+/// assert_eq!(*inserted.lock().expect("inserted"), 2);
+/// ```
+pub fn into_fn<MM>(mut machine: Machine<MMInt, MMInt, MM>) -> impl FnMut(MMInt) -> MachineResult<MMInt>
+where
+    MM: Calculator<MMInt, MMInt, Calculated = Phase<MMInt, MMInt>>,
+{
+    move |n| lru_calculate(&mut machine, n)
+}
+
 /// Do the calculation of a math machine using
 /// cache values to do lookups and cleanup using
 /// an LRU scheme.
 pub fn lru_calculate<T, I, MM>(mm: &mut Machine<T, I, MM>, n: I) -> MachineResult<T>
 where
     T: Clone + Debug + Default + Ord,
-    I: Clone + Debug + Copy + Default + Eq + Hash + Ord + PartialEq,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
     MM: Calculator<T, I, Calculated = Phase<T, I>>
 {
-    let mut phase = lru_find_phase(mm, n.clone());
-    lru_drop_if_capacity_met(mm);
-    lru_do_calculation(mm, n, &mut phase)
+    // Closed forms need no cache at all.
+    if let Some(direct) = mm.machine.as_direct() {
+        if !mm.machine.valid_input(&n) {
+            return Err(MachineError::InvalidInput);
+        }
+        return direct.calculate_direct(n);
+    }
+    lru_calculate_phase(mm, n).map(|calc| calc.result().to_owned())
 }
 
 /// Perform a raw calculation for the Nth value of
@@ -91,25 +760,44 @@ where
 pub fn raw_calculate<T, I, MM>(mm: &Machine<T, I, MM>, n: I) -> MachineResult<T>
 where
     T: Clone + Debug + Default + Ord,
-    I: Clone + Copy + Debug + Default + Eq + Hash + Ord + PartialEq,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
     MM: Calculator<T, I, Calculated = Phase<T, I>>,
 {
-    match mm.calculate(n, &mut MM::Calculated::new()) {
+    if !mm.machine.valid_input(&n) {
+        return Err(MachineError::InvalidInput);
+    }
+    let mut phase = MM::Calculated::new();
+    mm.machine.seed(&mut phase);
+    match mm.calculate(n, &mut phase) {
         Ok(calc) => Ok(calc.result().to_owned()),
         Err(m) => Err(m)
     }
 }
 
-fn lru_do_calculation<T, I, MM>(mm: &mut Machine<T, I, MM>, n: I, phase: &mut MM::Calculated) -> MachineResult<T>
+fn lru_calculate_phase<T, I, MM>(mm: &mut Machine<T, I, MM>, n: I) -> MachineResult<Phase<T, I>>
+where
+    T: Clone + Debug + Default + Ord,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
+    MM: Calculator<T, I, Calculated = Phase<T, I>>,
+{
+    if !mm.machine.valid_input(&n) {
+        return Err(MachineError::InvalidInput);
+    }
+    let mut phase = lru_find_phase(mm, n.clone());
+    lru_drop_if_capacity_met(mm);
+    lru_do_calculation(mm, n, &mut phase)
+}
+
+fn lru_do_calculation<T, I, MM>(mm: &mut Machine<T, I, MM>, n: I, phase: &mut MM::Calculated) -> MachineResult<Phase<T, I>>
 where
     T: Clone + Debug + Default + Ord,
-    I: Clone + Copy + Debug + Default + Eq + Hash + Ord + PartialEq,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
     MM: Calculator<T, I, Calculated = Phase<T, I>>,
 {
     match mm.calculate(n, phase) {
         Ok(calc) => {
             mm.update(calc.clone());
-            Ok(calc.result().to_owned())
+            Ok(calc)
         },
         Err(m) => Err(m)
     }
@@ -118,23 +806,28 @@ where
 fn lru_drop_if_capacity_met<T, I, MM>(mm: &mut Machine<T, I, MM>)
 where
     T: Clone + Debug + Default + Ord,
-    I: Clone + Copy + Debug + Default + Eq + Hash + Ord + PartialEq,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
     MM: Calculator<T, I>,
 {
-    if mm.is_too_big() || mm.is_too_old() {
-        let _ = mm.drop_invalid().expect("dropped values");
+    if mm.would_evict() {
+        let dropped = mm.drop_invalid().expect("dropped values");
+        if let Some(cb) = mm.on_evict.as_mut() {
+            dropped.iter().for_each(cb);
+        }
     }
 }
 
 fn lru_find_phase<T, I, MM>(mm: &mut Machine<T, I, MM>, n: I) -> Phase<T, I>
 where
     T: Clone + Debug + Default + Ord,
-    I: Clone + Copy + Debug + Default + Eq + Hash + Ord + PartialEq,
-    MM: Calculator<T, I>,
+    I: Clone + Debug + Default + Eq + Hash + Ord + PartialEq,
+    MM: Calculator<T, I, Calculated = Phase<T, I>>,
 {
     if let Ok(p) = mm.lookup(n) {
         p.to_owned()
     } else {
-        Phase::new()
+        let mut phase = Phase::new();
+        mm.machine.seed(&mut phase);
+        phase
     }
 }
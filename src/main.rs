@@ -1,5 +1,4 @@
 use math_machines::{MMInt, Machine, lru_calculate, Harmonic};
-use rand;
 
 fn main() {
     let mut machine = Machine::new(Harmonic{}, 128, 50);
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
 use std::ops::{Index, IndexMut};
 use std::slice::SliceIndex;
@@ -13,12 +14,62 @@ pub trait Newable {
 /// Variable type alias for the size of integer
 /// math machines use.
 pub type MMInt = u128;
+/// Variable type alias for the size of signed
+/// integer math machines use.
+pub type MMSInt = i128;
 /// Variable type alias for the size of float
 /// math machines use.
 pub type MMFlt = OrderedFloat<f64>;
 /// Variable type alias for the `size` type
 /// math machines use.
 pub type MMSize = usize;
+/// Float which compares by its value rounded to
+/// the nearest multiple of some epsilon, allowing
+/// caches to group float results which differ
+/// only by rounding. Values rounded alike are
+/// ordered by their epsilon, so the ordering is
+/// total.
+///
+/// ```
+/// use math_machines::phases::ApproxF64;
+///
+/// let a = ApproxF64{value: 1.0, eps: 1e-9};
+/// let b = ApproxF64{value: 1.0 + 1e-12, eps: 1e-9};
+/// let c = ApproxF64{value: 1.1, eps: 1e-9};
+/// assert_eq!(a, b);
+/// assert!(a < c);
+///
+/// let a = ApproxF64{value: 0.0, eps: 0.1};
+/// let b = ApproxF64{value: 0.08, eps: 0.1};
+/// let c = ApproxF64{value: 0.16, eps: 0.1};
+/// assert!(a < b && b < c);
+/// assert_eq!(b, ApproxF64{value: 0.12, eps: 0.1});
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApproxF64 {
+    pub value: f64,
+    pub eps: f64,
+}
+/// Value paired with the number of iterations
+/// taken to calculate it. Compares by its value
+/// alone, so caches keep one entry per value
+/// however many iterations were counted.
+///
+/// ```
+/// use math_machines::phases::Counted;
+///
+/// let a = Counted{value: 3, steps: 2};
+/// let b = Counted{value: 3, steps: 5};
+/// let c = Counted{value: 4, steps: 0};
+/// assert_eq!(a, b);
+/// assert!(b < c);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Counted<T> {
+    pub value: T,
+    pub steps: MMSize,
+}
 /// Actual, or internal, phase slice of a `Phase`
 /// instance.
 type PhaseActual<T> = [T; PHASE_SIZE];
@@ -30,12 +81,36 @@ type PhaseActual<T> = [T; PHASE_SIZE];
 /// `(2nd, 3rd, 4th, ...)` are the arguments to
 /// achieve said result.
 #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Phase<T, I> {
     phase:  PhaseActual<T>,
     input:  I,
 }
 
 impl<T: Default, I: Default> Phase<T, I> {
+    /// Create a new phase from a slice of values,
+    /// if the slice is the same length as the
+    /// phase.
+    ///
+    /// ```
+    /// use math_machines::phases::Phase;
+    ///
+    /// let phase = Phase::<u8, u8>::from_slice(&[3, 2, 1], 4).expect("a phase");
+    /// assert_eq!(*phase.result(), 3);
+    /// assert_eq!(*phase.input(), 4);
+    ///
+    /// assert!(Phase::<u8, u8>::from_slice(&[3, 2], 4).is_none());
+    /// assert!(Phase::<u8, u8>::from_slice(&[4, 3, 2, 1], 4).is_none());
+    /// ```
+    pub fn from_slice(s: &[T], input: I) -> Option<Self>
+    where
+        T: Clone
+    {
+        match s.to_vec().try_into() {
+            Ok(phase) => Some(Self {phase, input}),
+            Err(_) => None
+        }
+    }
     /// Returns the `N` of the function call this
     /// phase represents.
     pub fn input(&self) -> &I {
@@ -45,10 +120,47 @@ impl<T: Default, I: Default> Phase<T, I> {
     pub fn phase(&self) -> &PhaseActual<T> {
         &self.phase
     }
+    /// Returns the 1st value of the phase, the
+    /// value before the result in a recurrence, or
+    /// other state kept by a calculator.
+    ///
+    /// ```
+    /// use math_machines::phases::Phase;
+    ///
+    /// let mut phase = Phase::<u8, u8>::from_slice(&[3, 2, 1], 4).expect("a phase");
+    /// assert_eq!((*phase.result(), *phase.prev(), *phase.prev2()), (3, 2, 1));
+    ///
+    /// *phase.result_mut() = 30;
+    /// *phase.prev_mut()   = 20;
+    /// *phase.prev2_mut()  = 10;
+    /// assert_eq!(phase[..], [30, 20, 10]);
+    /// ```
+    pub fn prev(&self) -> &T {
+        &self.phase[1]
+    }
+    /// Mutable 1st value of the phase.
+    pub fn prev_mut(&mut self) -> &mut T {
+        &mut self.phase[1]
+    }
+    /// Returns the 2nd value of the phase, the
+    /// value two before the result in a
+    /// recurrence, or other state kept by a
+    /// calculator.
+    pub fn prev2(&self) -> &T {
+        &self.phase[2]
+    }
+    /// Mutable 2nd value of the phase.
+    pub fn prev2_mut(&mut self) -> &mut T {
+        &mut self.phase[2]
+    }
     /// Returns the result from the phase input.
     pub fn result(&self) -> &T {
         &self.phase[0]
     }
+    /// Mutable result of the phase.
+    pub fn result_mut(&mut self) -> &mut T {
+        &mut self.phase[0]
+    }
     /// Rotate phase elements to the right `K`
     /// places, preserving the `0th`and `1st`
     /// values in the phase.
@@ -56,11 +168,85 @@ impl<T: Default, I: Default> Phase<T, I> {
         self.phase.rotate_right(k)
     }
     /// Set the input of this phase.
-    pub fn setinput(&mut self, n: I)
-    where
-        I: Copy
-    {
-        self.input = n.to_owned();
+    pub fn setinput(&mut self, n: I) {
+        self.input = n;
+    }
+}
+
+impl<I: Clone> Phase<MMFlt, I> {
+    /// Copy this phase into one which compares
+    /// its values within the given epsilon.
+    pub fn approx(&self, eps: f64) -> Phase<ApproxF64, I> {
+        Phase {
+            phase: self.phase.map(|v| ApproxF64{value: v.0, eps}),
+            input: self.input.clone(),
+        }
+    }
+}
+
+impl<I: Clone> Phase<ApproxF64, I> {
+    /// Copy this phase into one which compares
+    /// its values exactly.
+    pub fn exact(&self) -> Phase<MMFlt, I> {
+        Phase {
+            phase: self.phase.map(|v| OrderedFloat(v.value)),
+            input: self.input.clone(),
+        }
+    }
+}
+
+impl ApproxF64 {
+    /// Value rounded to the nearest multiple of
+    /// the epsilon, or the value itself where
+    /// there is no epsilon.
+    fn rounded(&self) -> f64 {
+        if self.eps > 0.0 {
+            (self.value / self.eps).round() * self.eps
+        } else {
+            self.value
+        }
+    }
+}
+
+impl Eq for ApproxF64 {}
+
+impl Ord for ApproxF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rounded()
+            .total_cmp(&other.rounded())
+            .then(self.eps.total_cmp(&other.eps))
+    }
+}
+
+impl PartialEq for ApproxF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl PartialOrd for ApproxF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Eq> Eq for Counted<T> {}
+
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Ord> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,41 @@
+use crate::calculators::Calculator;
+use crate::machines::{Machine, lru_calculate};
+use crate::phases::{MMInt, Phase};
+
+use std::fmt::Debug;
+
+/// Assert the first values of a machine, from
+/// N of 0, match the expected values. Panics at
+/// the first divergence, naming the N, the
+/// expected and the calculated value.
+///
+/// ```
+/// use math_machines::{Machine, Fibonacci};
+/// use math_machines::testing::assert_sequence;
+///
+/// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+/// assert_sequence(machine, &[0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+/// ```
+///
+/// ```should_panic(expected = "diverged at N of 4")
+/// use math_machines::{Machine, Fibonacci};
+/// use math_machines::testing::assert_sequence;
+///
+/// let machine = &mut Machine::new(Fibonacci{}, 128, 50);
+/// assert_sequence(machine, &[0, 1, 1, 2, 4, 5]);
+/// ```
+pub fn assert_sequence<T, MM>(machine: &mut Machine<T, MMInt, MM>, expected: &[T])
+where
+    T: Clone + Debug + Default + Ord,
+    MM: Calculator<T, MMInt, Calculated = Phase<T, MMInt>>,
+{
+    for (n, value) in expected.iter().enumerate() {
+        match lru_calculate(machine, n as MMInt) {
+            Ok(calc) if calc == *value => continue,
+            Ok(calc) => panic!(
+                "sequence diverged at N of {n}: expected {value:?}, calculated {calc:?}"),
+            Err(err) => panic!(
+                "sequence diverged at N of {n}: expected {value:?}, calculation failed with {err:?}"),
+        }
+    }
+}